@@ -0,0 +1,114 @@
+//! # Citation Key Aliases
+//!
+//! Maps short citation keys, e.g., `jabs2024bimaxsat`, to DBLP keys. Aliased citations are
+//! fetched from DBLP but written under the alias key, with an `ids` field holding the DBLP key so
+//! that both keys resolve.
+//!
+//! The alias file is a TOML table of alias keys to DBLP keys, with or without the key prefix:
+//!
+//! ```toml
+//! jabs2024bimaxsat = "DBLP:journals/jair/JabsBNJ24"
+//! ```
+
+use std::collections::HashMap;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid alias file: {0}")]
+    Toml(#[from] toml::de::Error),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Aliases {
+    /// Maps alias keys to DBLP keys without prefix
+    to_dblp: HashMap<String, String>,
+}
+
+/// Cited keys with aliases resolved
+#[derive(Clone, Debug, Default)]
+pub struct Resolved {
+    /// The cited DBLP keys without prefix, sorted and deduplicated
+    pub keys: Vec<String>,
+    /// Maps DBLP keys without prefix to the alias they were cited as
+    pub aliases: HashMap<String, String>,
+}
+
+impl Aliases {
+    pub fn read<P: AsRef<camino::Utf8Path>>(path: P, prefix: &str) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path.as_ref())?;
+        Self::parse(&content, prefix)
+    }
+
+    pub fn parse(content: &str, prefix: &str) -> Result<Self, Error> {
+        let table: HashMap<String, String> = toml::from_str(content)?;
+        Ok(Self {
+            to_dblp: table
+                .into_iter()
+                .map(|(alias, key)| {
+                    let key = key.strip_prefix(prefix).map(String::from).unwrap_or(key);
+                    (alias, key)
+                })
+                .collect(),
+        })
+    }
+
+    /// Resolves cited keys to DBLP keys, dropping keys that are neither prefixed nor aliases
+    pub fn resolve<'a, I: IntoIterator<Item = &'a str>>(&self, keys: I, prefix: &str) -> Resolved {
+        let mut resolved = Resolved::default();
+        for key in keys {
+            if let Some(key) = key.strip_prefix(prefix) {
+                resolved.keys.push(String::from(key));
+            } else if let Some(dblp) = self.to_dblp.get(key) {
+                resolved.keys.push(dblp.clone());
+                resolved.aliases.insert(dblp.clone(), String::from(key));
+            }
+        }
+        resolved.keys.sort_unstable();
+        resolved.keys.dedup();
+        resolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn resolve() {
+        let aliases = super::Aliases::parse(
+            r#"
+jabs2024bimaxsat = "DBLP:journals/jair/JabsBNJ24"
+jabs2023preprocessing = "conf/cp/JabsBIJ23"
+"#,
+            "DBLP:",
+        )
+        .unwrap();
+        let resolved = aliases.resolve(
+            [
+                "jabs2024bimaxsat",
+                "DBLP:journals/jair/JabsBNJ24",
+                "jabs2023preprocessing",
+                "knuth1984",
+                "DBLP:conf/sat/DaviesB13",
+            ],
+            "DBLP:",
+        );
+        assert_eq!(
+            resolved.keys,
+            [
+                "conf/cp/JabsBIJ23",
+                "conf/sat/DaviesB13",
+                "journals/jair/JabsBNJ24"
+            ]
+        );
+        assert_eq!(resolved.aliases.len(), 2);
+        assert_eq!(
+            resolved.aliases["journals/jair/JabsBNJ24"],
+            "jabs2024bimaxsat"
+        );
+        assert_eq!(
+            resolved.aliases["conf/cp/JabsBIJ23"],
+            "jabs2023preprocessing"
+        );
+    }
+}
@@ -1,10 +1,15 @@
-use biblatex::Type;
+use biblatex::{ChunksExt, Type};
 
-use crate::dblp::{
-    Record,
-    record::{Crossref, External},
+use crate::{
+    dblp::{
+        Record,
+        record::{Crossref, External, Person},
+    },
+    fixers::math,
 };
 
+pub mod strings;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Invalid BibTeX Entry: {0}")]
@@ -13,19 +18,23 @@ pub enum Error {
     MissingField(String, String),
 }
 
-pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
+/// Parses DBLP entries from a BibTeX file
+///
+/// DBLP entries are those whose key starts with `prefix`, or that are written under an alias key
+/// and have a prefixed key in their `ids` field.
+pub fn parse(content: &str, prefix: &str) -> Result<Vec<Record>, Error> {
     let mut records = vec![];
-    for entry in biblatex::Bibliography::parse(&content)? {
-        if !entry.key.starts_with("DBLP:") {
+    for entry in biblatex::Bibliography::parse(content)? {
+        let Some(key) = dblp_key(&entry, prefix) else {
             eprintln!("Dropping non-DBLP entry in BibTeX file");
             eprintln!("{}", entry.to_biblatex_string());
             eprintln!("If this is important, move it to a separate BibTeX file");
-        }
-        let key = String::from(&entry.key[5..]);
+            continue;
+        };
         let Ok(title) = entry.title() else {
             return Err(Error::MissingField(String::from("title"), entry.key));
         };
-        let title = String::from_chunks(title).unwrap();
+        let title = format_title(title);
         match entry.entry_type {
             biblatex::EntryType::Article => {
                 let Ok(author) = entry.author() else {
@@ -50,7 +59,7 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                 }
                 records.push(Record::Article {
                     key,
-                    author: author.into_iter().map(format_person).collect(),
+                    author: people(&entry, "author", author),
                     title,
                     journal,
                     year,
@@ -59,6 +68,36 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                     external,
                 })
             }
+            // arXiv preprints in biblatex, DBLP lists them as CoRR articles
+            biblatex::EntryType::Online => {
+                let Ok(author) = entry.author() else {
+                    return Err(Error::MissingField(String::from("author"), entry.key));
+                };
+                let Ok(eprint) = entry.eprint() else {
+                    return Err(Error::MissingField(String::from("eprint"), entry.key));
+                };
+                let Ok(date) = entry.date() else {
+                    return Err(Error::MissingField(String::from("date"), entry.key));
+                };
+                let year = format_year(date);
+                let mut external = vec![];
+                if let Ok(doi) = entry.doi() {
+                    external.push(External::Doi(doi));
+                }
+                if let Ok(url) = entry.url() {
+                    external.push(External::Url(url));
+                }
+                records.push(Record::Article {
+                    key,
+                    author: people(&entry, "author", author),
+                    title,
+                    journal: String::from("CoRR"),
+                    year,
+                    pages: None,
+                    volume: Some(format!("abs/{eprint}")),
+                    external,
+                })
+            }
             biblatex::EntryType::Proceedings => {
                 let editors = entry.editors().unwrap_or(vec![]);
                 let editor = editors.into_iter().next().map(|(p, _)| p).unwrap_or(vec![]);
@@ -85,14 +124,14 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                     .map(|val| String::from_chunks(val).unwrap());
                 records.push(Record::Proceedings {
                     key,
-                    editor: editor.into_iter().map(format_person).collect(),
+                    editor: people(&entry, "editor", editor),
                     title,
                     year,
                     series,
                     volume,
                     publisher,
                     external,
-                    isbn: isbn.into_iter().collect(),
+                    isbn: split_isbn(isbn),
                 })
             }
             biblatex::EntryType::InProceedings => {
@@ -118,7 +157,7 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                 let crossref = match entry.get("crossref") {
                     Some(crossref) => {
                         let key = String::from_chunks(crossref).unwrap();
-                        let key = key.strip_prefix("DBLP:").unwrap_or(&key);
+                        let key = key.strip_prefix(prefix).unwrap_or(&key);
                         Crossref::Key(String::from(key))
                     }
                     None => {
@@ -133,19 +172,17 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                             .map(|val| String::from_chunks(val).unwrap());
                         let volume = entry.volume().ok().map(format_volume);
                         Crossref::Resolved {
-                            editor: editor.into_iter().map(format_person).collect(),
+                            editor: people(&entry, "editor", editor),
                             publisher,
                             series,
                             volume,
                         }
                     }
                 };
-                let usera = entry
-                    .get("usera")
-                    .map(|val| String::from_chunks(val).unwrap());
+                let usera = entry.get("usera").map(format_protected);
                 records.push(Record::Inproceedings {
                     key,
-                    author: author.into_iter().map(format_person).collect(),
+                    author: people(&entry, "author", author),
                     title,
                     booktitle,
                     year,
@@ -155,7 +192,7 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                     usera,
                 })
             }
-            biblatex::EntryType::Book => {
+            biblatex::EntryType::Book | biblatex::EntryType::Collection => {
                 let author = entry.author().unwrap_or(vec![]);
                 let editors = entry.editors().unwrap_or(vec![]);
                 let editor = editors.into_iter().next().map(|(p, _)| p).unwrap_or(vec![]);
@@ -182,15 +219,15 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                     .map(|val| String::from_chunks(val).unwrap());
                 records.push(Record::Book {
                     key,
-                    author: author.into_iter().map(format_person).collect(),
-                    editor: editor.into_iter().map(format_person).collect(),
+                    author: people(&entry, "author", author),
+                    editor: people(&entry, "editor", editor),
                     title,
                     publisher,
                     year,
                     series,
                     volume,
                     external,
-                    isbn: isbn.into_iter().collect(),
+                    isbn: split_isbn(isbn),
                 })
             }
             biblatex::EntryType::InCollection => {
@@ -216,7 +253,7 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                 let crossref = match entry.get("crossref") {
                     Some(crossref) => {
                         let key = String::from_chunks(crossref).unwrap();
-                        let key = key.strip_prefix("DBLP:").unwrap_or(&key);
+                        let key = key.strip_prefix(prefix).unwrap_or(&key);
                         Crossref::Key(String::from(key))
                     }
                     None => {
@@ -229,7 +266,7 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                             .map(|val| String::from_chunks(val).unwrap());
                         let volume = entry.volume().ok().map(format_volume);
                         Crossref::Resolved {
-                            editor: editor.into_iter().map(format_person).collect(),
+                            editor: people(&entry, "editor", editor),
                             publisher,
                             series,
                             volume,
@@ -238,7 +275,7 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                 };
                 records.push(Record::Incollection {
                     key,
-                    author: author.into_iter().map(format_person).collect(),
+                    author: people(&entry, "author", author),
                     title,
                     booktitle,
                     year,
@@ -247,7 +284,7 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                     crossref,
                 })
             }
-            biblatex::EntryType::Misc => {
+            biblatex::EntryType::Misc | biblatex::EntryType::Dataset => {
                 let Ok(author) = entry.author() else {
                     return Err(Error::MissingField(String::from("author"), entry.key));
                 };
@@ -265,7 +302,7 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
                 }
                 records.push(Record::Misc {
                     key,
-                    author: author.into_iter().map(format_person).collect(),
+                    author: people(&entry, "author", author),
                     title,
                     year,
                     publisher,
@@ -282,6 +319,53 @@ pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
     Ok(records)
 }
 
+/// Gets the DBLP key without prefix of an entry, either from the key or the `ids` field
+fn dblp_key(entry: &biblatex::Entry, prefix: &str) -> Option<String> {
+    if let Some(key) = entry.key.strip_prefix(prefix) {
+        return Some(String::from(key));
+    }
+    let ids = String::from_chunks(entry.get("ids")?).ok()?;
+    ids.split(',')
+        .find_map(|id| id.trim().strip_prefix(prefix))
+        .map(String::from)
+}
+
+/// Converts people, including their ORCID from a biblatex `<field>+an:orcid` annotation
+fn people(entry: &biblatex::Entry, field: &str, people: Vec<biblatex::Person>) -> Vec<Person> {
+    let orcids = entry
+        .get(&format!("{field}+an:orcid"))
+        .and_then(|chunks| String::from_chunks(chunks).ok())
+        .unwrap_or_default();
+    let mut people: Vec<Person> = people
+        .into_iter()
+        .map(format_person)
+        .map(Person::from)
+        .collect();
+    for annotation in orcids.split(';') {
+        let Some((idx, orcid)) = annotation.split_once('=') else {
+            continue;
+        };
+        let Some(person) = idx
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|idx| people.get_mut(idx.checked_sub(1)?))
+        else {
+            continue;
+        };
+        person.orcid = Some(String::from(orcid.trim().trim_matches('"')));
+    }
+    people
+}
+
+/// Splits an `isbn` field that lists several ISBNs, as written for biblatex
+fn split_isbn(isbn: Option<String>) -> Vec<String> {
+    isbn.iter()
+        .flat_map(|isbn| isbn.split(','))
+        .map(|isbn| String::from(isbn.trim()))
+        .collect()
+}
+
 fn format_person(person: biblatex::Person) -> String {
     if person.suffix.is_empty() {
         if person.prefix.is_empty() {
@@ -329,6 +413,32 @@ fn format_volume(volume: biblatex::PermissiveType<i64>) -> String {
     }
 }
 
+/// Formats a title, marking inline math like the math fixer does to keep it apart from literal
+/// dollar signs
+fn format_title(chunks: biblatex::ChunksRef) -> String {
+    let marked: Vec<_> = chunks
+        .iter()
+        .map(|chunk| match &chunk.v {
+            biblatex::Chunk::Math(math) => biblatex::Spanned::new(
+                biblatex::Chunk::Verbatim(format!("{DELIM}{math}{DELIM}", DELIM = math::DELIM)),
+                chunk.span.clone(),
+            ),
+            _ => chunk.clone(),
+        })
+        .collect();
+    marked.format_verbatim()
+}
+
+/// Formats a free text field, keeping the braces that protect parts of it
+fn format_protected(chunks: biblatex::ChunksRef) -> String {
+    let braced = chunks.to_biblatex_string(false);
+    let inner = braced
+        .strip_prefix('{')
+        .and_then(|braced| braced.strip_suffix('}'))
+        .expect("chunks are written in braces");
+    String::from(inner)
+}
+
 fn format_publisher(publisher: Vec<biblatex::Chunks>) -> String {
     assert_eq!(publisher.len(), 1);
     let publisher = publisher.into_iter().next().unwrap();
@@ -344,6 +454,21 @@ mod tests {
             crossref: true,
             all_externals: false,
             dont_expand_journals: false,
+            venue_style: Default::default(),
+            key_prefix: String::from("DBLP:"),
+            output_format: Default::default(),
+            orcid: false,
+            corrections: None,
+            dictionaries: vec![],
+            casing: None,
+            explain: false,
+            explain_log: None,
+            fixers: Default::default(),
+            pipeline: crate::fixers::pipeline::Pipeline::builtin(
+                crate::fixers::dictionary::Dictionary::builtin(),
+                crate::fixers::corrections::Corrections::builtin(),
+                Default::default(),
+            ),
         };
 
         let orig = r#"@article{DBLP:journals/jair/JabsBNJ24,
@@ -355,8 +480,8 @@ mod tests {
   volume       = {80},
   doi          = {10.1613/jair.1.15333},
 }"#;
-        let mut parsed = super::parse(orig).unwrap();
-        super::super::fixup(&mut parsed[0], &args);
+        let mut parsed = super::parse(orig, "DBLP:").unwrap();
+        super::super::fixup(&mut parsed[0], &args, crate::cli::Color::Never);
         assert_eq!(&format!("{}", parsed[0].bibtex()), orig);
 
         let orig = r#"@proceedings{DBLP:conf/cpaior/2024-2,
@@ -369,8 +494,8 @@ mod tests {
   isbn         = {978-3-031-60601-4},
   doi          = {10.1007/978-3-031-60599-4},
 }"#;
-        let mut parsed = super::parse(orig).unwrap();
-        super::super::fixup(&mut parsed[0], &args);
+        let mut parsed = super::parse(orig, "DBLP:").unwrap();
+        super::super::fixup(&mut parsed[0], &args, crate::cli::Color::Never);
         assert_eq!(&format!("{}", parsed[0].bibtex()), orig);
 
         let orig = r#"@inproceedings{DBLP:conf/cp/JabsBIJ23,
@@ -386,8 +511,8 @@ mod tests {
   publisher    = {Schloss Dagstuhl - Leibniz-Zentrum f{\"u}r Informatik},
   usera        = {Proceedings of International Conference on Principles and Practice of Constraint Programming, {CP} 2023},
 }"#;
-        let mut parsed = super::parse(orig).unwrap();
-        super::super::fixup(&mut parsed[0], &args);
+        let mut parsed = super::parse(orig, "DBLP:").unwrap();
+        super::super::fixup(&mut parsed[0], &args, crate::cli::Color::Never);
         assert_eq!(&format!("{}", parsed[0].bibtex()), orig);
 
         let orig = r#"@inproceedings{DBLP:conf/jelia/JabsBJ25,
@@ -400,8 +525,296 @@ mod tests {
   crossref     = {DBLP:conf/jelia/2025-1},
   usera        = {Logics in Artificial Intelligence, {JELIA} 2025},
 }"#;
-        let mut parsed = super::parse(orig).unwrap();
-        super::super::fixup(&mut parsed[0], &args);
+        let mut parsed = super::parse(orig, "DBLP:").unwrap();
+        super::super::fixup(&mut parsed[0], &args, crate::cli::Color::Never);
         assert_eq!(&format!("{}", parsed[0].bibtex()), orig);
+
+        // inline math is kept, literal dollar signs stay escaped
+        let orig = r#"@article{DBLP:journals/jair/JabsBNJ24,
+  author       = {Jabs, Christoph and Berg, Jeremias and Niskanen, Andreas and J{\"a}rvisalo, Matti},
+  title        = {Solving $k$-{SAT} for \$5 instead of \$10},
+  journal      = {Journal of Artificial Intelligence Research},
+  year         = {2024},
+  pages        = {1223--1269},
+  volume       = {80},
+  doi          = {10.1613/jair.1.15333},
+}"#;
+        let mut parsed = super::parse(orig, "DBLP:").unwrap();
+        super::super::fixup(&mut parsed[0], &args, crate::cli::Color::Never);
+        assert_eq!(&format!("{}", parsed[0].bibtex()), orig);
+    }
+
+    #[test]
+    fn alias_roundtrip() {
+        let args = crate::cli::CommonGetArgs {
+            unicode: false,
+            crossref: true,
+            all_externals: false,
+            dont_expand_journals: false,
+            venue_style: Default::default(),
+            key_prefix: String::from("DBLP:"),
+            output_format: Default::default(),
+            orcid: false,
+            corrections: None,
+            dictionaries: vec![],
+            casing: None,
+            explain: false,
+            explain_log: None,
+            fixers: Default::default(),
+            pipeline: crate::fixers::pipeline::Pipeline::builtin(
+                crate::fixers::dictionary::Dictionary::builtin(),
+                Default::default(),
+                Default::default(),
+            ),
+        };
+
+        let orig = r#"@article{jabs2024bimaxsat,
+  ids          = {DBLP:journals/jair/JabsBNJ24},
+  author       = {Jabs, Christoph and Berg, Jeremias and Niskanen, Andreas and J{\"a}rvisalo, Matti},
+  title        = {From Single-Objective to Bi-Objective Maximum Satisfiability Solving},
+  journal      = {Journal of Artificial Intelligence Research},
+  year         = {2024},
+  pages        = {1223--1269},
+  volume       = {80},
+  doi          = {10.1613/jair.1.15333},
+}"#;
+        let mut parsed = super::parse(orig, "DBLP:").unwrap();
+        assert_eq!(parsed[0].key(), "journals/jair/JabsBNJ24");
+        super::super::fixup(&mut parsed[0], &args, crate::cli::Color::Never);
+        let mut bibtex = parsed[0].bibtex();
+        bibtex.alias("jabs2024bimaxsat");
+        assert_eq!(&format!("{bibtex}"), orig);
+
+        // non-DBLP entries are dropped
+        let other = "@misc{knuth1984, title = {Literate Programming}, year = {1984}}";
+        assert!(super::parse(other, "DBLP:").unwrap().is_empty());
+    }
+
+    #[test]
+    fn orcid_roundtrip() {
+        let mut args = crate::cli::CommonGetArgs {
+            unicode: false,
+            crossref: true,
+            all_externals: false,
+            dont_expand_journals: false,
+            venue_style: Default::default(),
+            key_prefix: String::from("DBLP:"),
+            output_format: Default::default(),
+            orcid: true,
+            corrections: None,
+            dictionaries: vec![],
+            casing: None,
+            explain: false,
+            explain_log: None,
+            fixers: Default::default(),
+            pipeline: Default::default(),
+        };
+        args.build_pipeline().unwrap();
+
+        let orig = r#"@article{DBLP:journals/jair/JabsBNJ24,
+  author       = {Jabs, Christoph and Berg, Jeremias and Niskanen, Andreas and J{\"a}rvisalo, Matti},
+  author+an:orcid = {1="0000-0003-3532-696X"; 2="0000-0001-7660-8061"},
+  title        = {From Single-Objective to Bi-Objective Maximum Satisfiability Solving},
+  journal      = {Journal of Artificial Intelligence Research},
+  year         = {2024},
+  pages        = {1223--1269},
+  volume       = {80},
+  doi          = {10.1613/jair.1.15333},
+}"#;
+        let mut parsed = super::parse(orig, "DBLP:").unwrap();
+        let crate::dblp::Record::Article { author, .. } = &parsed[0] else {
+            unreachable!()
+        };
+        assert_eq!(author[1].orcid.as_deref(), Some("0000-0001-7660-8061"));
+        assert_eq!(author[2].orcid, None);
+        super::super::fixup(&mut parsed[0], &args, crate::cli::Color::Never);
+        let mut bibtex = parsed[0].bibtex();
+        bibtex.orcid(args.orcid);
+        assert_eq!(&format!("{bibtex}"), orig);
+    }
+
+    #[test]
+    fn protected_usera() {
+        let parsed = super::parse(
+            r#"@inproceedings{DBLP:conf/sat/JabsBJ24,
+  author       = {Jabs, Christoph and Berg, Jeremias and J{\"a}rvisalo, Matti},
+  title        = {Core Boosting in SAT-Based Multi-objective Optimization},
+  booktitle    = {Theory and Applications of Satisfiability Testing, {SAT} 2024},
+  year         = {2024},
+  usera        = {Theory and Applications of Satisfiability Testing, {SAT} 2024},
+}"#,
+            "DBLP:",
+        )
+        .unwrap();
+        let crate::dblp::Record::Inproceedings { usera, .. } = &parsed[0] else {
+            unreachable!()
+        };
+        assert_eq!(
+            usera.as_deref(),
+            Some("Theory and Applications of Satisfiability Testing, {SAT} 2024")
+        );
+    }
+
+    #[test]
+    fn string_macros_roundtrip() {
+        let orig = r#"@string{dblp_lncs = {Lecture Notes in Computer Science}}
+@string{dblp_springer = {Springer}}
+
+@proceedings{DBLP:conf/cpaior/2024-1,
+  title        = {Integration of Constraint Programming, Artificial Intelligence, and Operations Research, Part I},
+  year         = {2024},
+  series       = dblp_lncs,
+  volume       = {14742},
+  publisher    = dblp_springer,
+}
+
+@proceedings{DBLP:conf/cpaior/2024-2,
+  title        = {Integration of Constraint Programming, Artificial Intelligence, and Operations Research, Part II},
+  year         = {2024},
+  series       = dblp_lncs,
+  volume       = {14743},
+  publisher    = dblp_springer,
+}"#;
+        let parsed = super::parse(orig, "DBLP:").unwrap();
+        let crate::dblp::Record::Proceedings {
+            series, publisher, ..
+        } = &parsed[0]
+        else {
+            unreachable!()
+        };
+        assert_eq!(series.as_deref(), Some("Lecture Notes in Computer Science"));
+        assert_eq!(publisher.as_deref(), Some("Springer"));
+
+        let strings = super::strings::Strings::collect(&parsed);
+        let mut out = format!("{strings}");
+        for rec in &parsed {
+            let mut bibtex = rec.bibtex();
+            bibtex.strings(&strings);
+            out.push_str(&format!("\n{bibtex}\n"));
+        }
+        assert_eq!(out.trim_end(), orig);
+    }
+
+    #[test]
+    fn biblatex_roundtrips() {
+        let mut args = crate::cli::CommonGetArgs {
+            unicode: false,
+            crossref: true,
+            all_externals: false,
+            dont_expand_journals: false,
+            venue_style: Default::default(),
+            key_prefix: String::from("DBLP:"),
+            output_format: crate::export::Format::Biblatex,
+            orcid: false,
+            corrections: None,
+            dictionaries: vec![],
+            casing: None,
+            explain: false,
+            explain_log: None,
+            fixers: Default::default(),
+            pipeline: Default::default(),
+        };
+        args.build_pipeline().unwrap();
+        let roundtrip = |orig: &str| {
+            let mut parsed = super::parse(orig, "DBLP:").unwrap();
+            super::super::fixup(&mut parsed[0], &args, crate::cli::Color::Never);
+            let mut bibtex = parsed[0].bibtex();
+            bibtex.flavor(crate::dblp::record::Flavor::Biblatex);
+            assert_eq!(&format!("{bibtex}"), orig);
+            parsed.remove(0)
+        };
+
+        roundtrip(
+            r#"@article{DBLP:journals/jair/JabsBNJ24,
+  author       = {Jabs, Christoph and Berg, Jeremias and Niskanen, Andreas and J{\"a}rvisalo, Matti},
+  title        = {From Single-Objective to Bi-Objective Maximum Satisfiability Solving},
+  journaltitle = {Journal of Artificial Intelligence Research},
+  date         = {2024},
+  pages        = {1223--1269},
+  volume       = {80},
+  doi          = {10.1613/jair.1.15333},
+}"#,
+        );
+
+        let rec = roundtrip(
+            r#"@online{DBLP:journals/corr/abs-2312-07085,
+  author       = {Jabs, Christoph and Berg, Jeremias and J{\"a}rvisalo, Matti},
+  title        = {Core Boosting in {SAT}-Based Multi-Objective Optimization},
+  date         = {2023},
+  eprint       = {2312.07085},
+  eprinttype   = {arXiv},
+  doi          = {10.48550/ARXIV.2312.07085},
+  url          = {https://arxiv.org/abs/2312.07085},
+}"#,
+        );
+        let crate::dblp::Record::Article {
+            journal, volume, ..
+        } = &rec
+        else {
+            unreachable!()
+        };
+        assert_eq!(journal, "CoRR");
+        assert_eq!(volume.as_deref(), Some("abs/2312.07085"));
+
+        let rec = roundtrip(
+            r#"@proceedings{DBLP:conf/cpaior/2024-2,
+  editor       = {Dilkina, Bistra},
+  title        = {Integration of Constraint Programming, Artificial Intelligence, and Operations Research{\textemdash}21st International Conference, {CPAIOR} 2024, Uppsala, Sweden, May 28{\textendash}31, 2024, Proceedings, Part {II}},
+  date         = {2024},
+  series       = {Lecture Notes in Computer Science},
+  volume       = {14743},
+  publisher    = {Springer},
+  isbn         = {978-3-031-60601-4, 978-3-031-60599-4},
+  doi          = {10.1007/978-3-031-60599-4},
+}"#,
+        );
+        let crate::dblp::Record::Proceedings { isbn, .. } = &rec else {
+            unreachable!()
+        };
+        assert_eq!(isbn, &["978-3-031-60601-4", "978-3-031-60599-4"]);
+
+        // the same record in classic BibTeX
+        let mut bibtex = rec.bibtex();
+        bibtex.flavor(crate::dblp::record::Flavor::Bibtex);
+        assert_eq!(
+            &format!("{bibtex}"),
+            r#"@proceedings{DBLP:conf/cpaior/2024-2,
+  editor       = {Dilkina, Bistra},
+  title        = {Integration of Constraint Programming, Artificial Intelligence, and Operations Research{\textemdash}21st International Conference, {CPAIOR} 2024, Uppsala, Sweden, May 28{\textendash}31, 2024, Proceedings, Part {II}},
+  year         = {2024},
+  series       = {Lecture Notes in Computer Science},
+  volume       = {14743},
+  publisher    = {Springer},
+  isbn         = {978-3-031-60601-4},
+  isbn         = {978-3-031-60599-4},
+  doi          = {10.1007/978-3-031-60599-4},
+}"#
+        );
+
+        roundtrip(
+            r#"@inproceedings{DBLP:conf/jelia/JabsBJ25,
+  author       = {Jabs, Christoph and Berg, Jeremias and J{\"a}rvisalo, Matti},
+  title        = {Engineering and Evaluating Multi-objective Pseudo-{Boolean} Optimizers},
+  booktitle    = {Logics in Artificial Intelligence{\textemdash}19th European Conference, {JELIA} 2025, Kutaisi, Georgia, September 1{\textendash}4, 2025, Proceedings, Part I},
+  date         = {2025},
+  pages        = {115--134},
+  doi          = {10.1007/978-3-032-04587-4_8},
+  crossref     = {DBLP:conf/jelia/2025-1},
+  usera        = {Logics in Artificial Intelligence, {JELIA} 2025},
+}"#,
+        );
+
+        roundtrip(
+            r#"@collection{DBLP:books/ios/21/BHvMW21,
+  editor       = {Biere, Armin and Heule, Marijn and van Maaren, Hans and Walsh, Toby},
+  title        = {Handbook of Satisfiability{\textemdash}Second Edition},
+  publisher    = {IOS Press},
+  date         = {2021},
+  series       = {Frontiers in Artificial Intelligence and Applications},
+  volume       = {336},
+  isbn         = {978-1-64368-160-3},
+  doi          = {10.3233/FAIA336},
+}"#,
+        );
     }
 }
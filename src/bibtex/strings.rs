@@ -0,0 +1,187 @@
+//! # String Macros
+//!
+//! Venue names, publishers and series that are repeated across entries are written once as
+//! `@string` definitions at the top of the BibTeX file and referenced by name in the entries:
+//!
+//! ```bibtex
+//! @string{dblp_jair = {Journal of Artificial Intelligence Research}}
+//!
+//! @article{DBLP:journals/jair/JabsBNJ24,
+//!   journal      = dblp_jair,
+//! ```
+//!
+//! The macro names are prefixed, so that they don't clash with the user's own macros or the month
+//! macros predefined by BibTeX.
+
+use std::{collections::HashMap, fmt};
+
+use crate::dblp::{
+    Record,
+    record::{Crossref, venue_key},
+};
+
+/// The prefix of all generated macro names
+const PREFIX: &str = "dblp_";
+
+/// String macros for values repeated across records
+#[derive(Clone, Debug, Default)]
+pub struct Strings {
+    /// Maps values to macro names
+    names: HashMap<String, String>,
+    /// Definitions sorted by name
+    definitions: Vec<(String, String)>,
+}
+
+/// The fields of a record that can be replaced by a macro, with a suggested macro name
+fn candidates(rec: &Record) -> Vec<(String, &str)> {
+    let venue = venue_key(rec.key());
+    let mut candidates = vec![];
+    match rec {
+        Record::Article { journal, .. } => candidates.push((String::from(venue), journal.as_str())),
+        Record::Inproceedings {
+            booktitle,
+            year,
+            crossref,
+            ..
+        }
+        | Record::Incollection {
+            booktitle,
+            year,
+            crossref,
+            ..
+        } => {
+            candidates.push((format!("{venue}{year}"), booktitle.as_str()));
+            if let Crossref::Resolved {
+                publisher, series, ..
+            } = crossref
+            {
+                candidates.extend(by_words(publisher.iter().chain(series)));
+            }
+        }
+        Record::Proceedings {
+            publisher, series, ..
+        }
+        | Record::Book {
+            publisher, series, ..
+        } => candidates.extend(by_words(publisher.iter().chain(series))),
+        Record::Misc { publisher, .. } => candidates.extend(by_words(publisher)),
+    }
+    candidates
+}
+
+/// Values with macro names suggested from their words
+fn by_words<'a>(
+    values: impl IntoIterator<Item = &'a String>,
+) -> impl Iterator<Item = (String, &'a str)> {
+    values
+        .into_iter()
+        .map(|value| (name_from_words(value), value.as_str()))
+}
+
+/// A macro name from the words of a value, e.g., `lncs` for `Lecture Notes in Computer Science`
+fn name_from_words(value: &str) -> String {
+    let words: Vec<_> = value
+        .split(|ch: char| ch.is_whitespace() || ch == '-')
+        .filter(|word| word.chars().next().is_some_and(|ch| !ch.is_lowercase()))
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .collect();
+    if words.len() > 2 {
+        words
+            .iter()
+            .filter_map(|word| word.chars().find(|ch| ch.is_alphanumeric()))
+            .collect()
+    } else {
+        words.concat()
+    }
+}
+
+/// Makes a valid BibTeX macro name that is not used yet
+fn unique_name(suggested: &str, used: &HashMap<String, String>) -> String {
+    let name = format!(
+        "{PREFIX}{}",
+        suggested
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_lowercase()
+    );
+    let taken = |name: &str| used.values().any(|used| used == name);
+    if !taken(&name) {
+        return name;
+    }
+    (2..)
+        .map(|idx| format!("{name}_{idx}"))
+        .find(|name| !taken(name))
+        .expect("there is always an unused name")
+}
+
+impl Strings {
+    /// Collects macros for all values that occur in more than one record
+    pub fn collect<'a>(records: impl IntoIterator<Item = &'a Record>) -> Self {
+        let mut counts: HashMap<&str, (usize, String)> = HashMap::new();
+        let mut order = vec![];
+        for rec in records {
+            for (suggested, value) in candidates(rec) {
+                let entry = counts.entry(value).or_insert_with(|| {
+                    order.push(value);
+                    (0, suggested)
+                });
+                entry.0 += 1;
+            }
+        }
+        let mut strings = Self::default();
+        for value in order {
+            let (count, suggested) = &counts[value];
+            if *count < 2 {
+                continue;
+            }
+            let name = unique_name(suggested, &strings.names);
+            strings.names.insert(String::from(value), name.clone());
+            strings.definitions.push((name, String::from(value)));
+        }
+        strings.definitions.sort_unstable();
+        strings
+    }
+
+    /// The macro name for a value
+    pub fn get(&self, value: &str) -> Option<&str> {
+        self.names.get(value).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+}
+
+impl fmt::Display for Strings {
+    /// Writes the `@string` definitions, one per line
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.definitions {
+            writeln!(f, "@string{{{name} = {{{value}}}}}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn names() {
+        assert_eq!(
+            super::name_from_words("Lecture Notes in Computer Science"),
+            "LNCS"
+        );
+        assert_eq!(super::name_from_words("IOS Press"), "IOSPress");
+        assert_eq!(
+            super::name_from_words("Schloss Dagstuhl - Leibniz-Zentrum für Informatik"),
+            "SDLZI"
+        );
+
+        let mut used = std::collections::HashMap::new();
+        assert_eq!(super::unique_name("LNCS", &used), "dblp_lncs");
+        used.insert(String::from("a"), String::from("dblp_lncs"));
+        assert_eq!(super::unique_name("LNCS", &used), "dblp_lncs_2");
+        assert_eq!(super::unique_name("dec", &used), "dblp_dec");
+        assert_eq!(super::unique_name("2024", &used), "dblp_2024");
+    }
+}
@@ -0,0 +1,19 @@
+//! # Citations
+//!
+//! Citation key sources for documents that are not written in LaTeX. The LaTeX sources live in
+//! [`crate::latex`].
+
+pub mod pandoc;
+pub mod typst;
+
+/// The citations of a document
+#[derive(Clone, Debug, Default)]
+pub struct Citations {
+    pub keys: Vec<String>,
+    /// Whether all entries of the bibliography are cited, e.g., via `\nocite{*}`
+    pub nocite_all: bool,
+    /// The bibliography databases referenced by the document
+    pub bib_files: Vec<camino::Utf8PathBuf>,
+    /// The files the citations were read from
+    pub files: Vec<camino::Utf8PathBuf>,
+}
@@ -0,0 +1,152 @@
+//! # Pandoc Markdown
+//!
+//! Extracts citations in Pandoc's citation syntax, e.g., `[@DBLP:conf/x/Y24; @key, p. 3]`,
+//! `-@key` or `@key` in text, and the bibliography databases from the YAML metadata block.
+
+use std::{fs, io};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::Citations;
+
+lazy_static! {
+    static ref CITATION_PATTERN: Regex = Regex::new(
+        r"(?m)(?:^|[^\w@\\])-?@(?:\{(?P<braced>[^{}\s]+)\}|(?P<key>\w(?:[\w:.#$%&+?<>~/-]*\w)?)|(?P<all>\*))"
+    )
+    .unwrap();
+    static ref INLINE_CODE_PATTERN: Regex = Regex::new(r"`[^`]*`").unwrap();
+}
+
+/// Scans a Pandoc Markdown file for citations
+pub fn scan<P: AsRef<camino::Utf8Path>>(path: P) -> io::Result<Citations> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)?;
+    let mut base_path = path.to_path_buf();
+    base_path.pop();
+
+    let mut citations = parse(&content);
+    citations.bib_files = bibliography(&content)
+        .into_iter()
+        .map(|name| base_path.join(name))
+        .collect();
+    citations.files = vec![path.to_path_buf()];
+    Ok(citations)
+}
+
+fn parse(content: &str) -> Citations {
+    let mut citations = Citations::default();
+    let mut fence: Option<&str> = None;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            continue;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            continue;
+        }
+        let line = INLINE_CODE_PATTERN.replace_all(line, "");
+        for captures in CITATION_PATTERN.captures_iter(&line) {
+            if captures.name("all").is_some() {
+                citations.nocite_all = true;
+            } else if let Some(key) = captures.name("braced").or(captures.name("key")) {
+                citations.keys.push(String::from(key.as_str()));
+            }
+        }
+    }
+    citations
+}
+
+/// Extracts the `bibliography` field from the YAML metadata block at the start of the document
+fn bibliography(content: &str) -> Vec<String> {
+    let mut lines = content.lines();
+    if lines.next().map(str::trim_end) != Some("---") {
+        return vec![];
+    }
+    let unquote = |val: &str| String::from(val.trim().trim_matches(['"', '\'']));
+    let mut files = vec![];
+    let mut in_list = false;
+    for line in lines {
+        if matches!(line.trim_end(), "---" | "...") {
+            break;
+        }
+        if in_list {
+            if let Some(item) = line.trim_start().strip_prefix("- ") {
+                files.push(unquote(item));
+                continue;
+            }
+            in_list = false;
+        }
+        let Some(value) = line.strip_prefix("bibliography:") else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            in_list = true;
+        } else if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            files.extend(list.split(',').map(unquote));
+        } else {
+            files.push(unquote(value));
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn citations() {
+        let content = r"---
+title: A Paper
+bibliography:
+  - refs.bib
+  - 'dblp.bib'
+nocite: |
+  @*
+---
+
+As shown in [@DBLP:conf/cp/JabsBIJ23; @knuth1984, p. 3], and by
+@DBLP:journals/jair/JabsBNJ24. Also see [-@{DBLP:conf/sat/DaviesB13}].
+Mail me at someone@example.com, this is not \@cited.
+
+```
+@DBLP:conf/code/Block24
+```
+
+Inline `@DBLP:conf/code/Inline24` code.
+";
+        let citations = super::parse(content);
+        assert_eq!(
+            citations.keys,
+            [
+                "DBLP:conf/cp/JabsBIJ23",
+                "knuth1984",
+                "DBLP:journals/jair/JabsBNJ24",
+                "DBLP:conf/sat/DaviesB13",
+            ]
+        );
+        assert!(citations.nocite_all);
+        assert_eq!(super::bibliography(content), ["refs.bib", "dblp.bib"]);
+    }
+
+    #[test]
+    fn bibliography() {
+        assert_eq!(
+            super::bibliography("---\nbibliography: dblp.bib\n---\n"),
+            ["dblp.bib"]
+        );
+        assert_eq!(
+            super::bibliography("---\nbibliography: [refs.bib, \"dblp.bib\"]\n...\n"),
+            ["refs.bib", "dblp.bib"]
+        );
+        assert!(super::bibliography("no metadata\nbibliography: x.bib\n").is_empty());
+    }
+}
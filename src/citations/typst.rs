@@ -0,0 +1,146 @@
+//! # Typst
+//!
+//! Extracts references from Typst documents, both in the `@key` shorthand and as
+//! `#cite(<key>)`. Keys may contain `/` and `:` as DBLP keys do. This follows `#include` and
+//! extracts the bibliography databases from `#bibliography(..)`.
+
+use std::{collections::HashSet, fs, io};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::Citations;
+
+lazy_static! {
+    static ref REFERENCE_PATTERN: Regex =
+        Regex::new(r"(?m)(?:^|[^\w@\\])@(?P<key>\w[\w:./-]*)").unwrap();
+    static ref CITE_PATTERN: Regex =
+        Regex::new(r#"cite\(\s*(?:<(?P<label>[^>\s]+)>|label\(\s*"(?P<string>[^"]+)"\s*\))"#)
+            .unwrap();
+    static ref BIBLIOGRAPHY_PATTERN: Regex = Regex::new(r"bibliography\((?P<args>.*)\)").unwrap();
+    static ref INCLUDE_PATTERN: Regex = Regex::new(r#"#include\s+"(?P<path>[^"]+)""#).unwrap();
+    static ref STRING_PATTERN: Regex = Regex::new(r#""(?P<val>[^"]*)""#).unwrap();
+    static ref BLOCK_COMMENT_PATTERN: Regex = Regex::new(r"(?s)/\*.*?\*/").unwrap();
+    static ref LINE_COMMENT_PATTERN: Regex = Regex::new(r"(?m)(?:^|\s)//.*$").unwrap();
+    static ref RAW_PATTERN: Regex = Regex::new(r"(?s)```.*?```|`[^`]*`").unwrap();
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Parsed {
+    keys: Vec<String>,
+    full_bibliography: bool,
+    bibliographies: Vec<String>,
+    includes: Vec<String>,
+}
+
+/// Scans a Typst file and all files it includes for references
+pub fn scan<P: AsRef<camino::Utf8Path>>(path: P) -> io::Result<Citations> {
+    let mut citations = Citations::default();
+    let mut visited = HashSet::new();
+    let mut stack = vec![(path.as_ref().to_path_buf(), true)];
+    while let Some((path, is_root)) = stack.pop() {
+        if !visited.insert(path.clone()) {
+            continue;
+        }
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            // the root file not existing is a hard error
+            Err(error) if is_root => return Err(error),
+            Err(error) => {
+                // soft fail of error when reading input file
+                crate::cli::warning!("Typst", "failed to read included file `{path}`: `{error}`");
+                continue;
+            }
+        };
+        citations.files.push(path.clone());
+        let mut dir = path.clone();
+        dir.pop();
+        let parsed = parse(&content);
+        citations.keys.extend(parsed.keys);
+        citations.nocite_all |= parsed.full_bibliography;
+        citations
+            .bib_files
+            .extend(parsed.bibliographies.iter().map(|name| dir.join(name)));
+        // push in reverse to visit includes in document order
+        stack.extend(
+            parsed
+                .includes
+                .iter()
+                .rev()
+                .map(|name| (dir.join(name), false)),
+        );
+    }
+    Ok(citations)
+}
+
+fn parse(content: &str) -> Parsed {
+    let content = BLOCK_COMMENT_PATTERN.replace_all(content, "");
+    let content = LINE_COMMENT_PATTERN.replace_all(&content, "");
+    let content = RAW_PATTERN.replace_all(&content, "");
+
+    let mut parsed = Parsed::default();
+    let mut refs: Vec<_> = REFERENCE_PATTERN
+        .captures_iter(&content)
+        .map(|cap| {
+            let key = cap.name("key").unwrap();
+            // trailing punctuation is not part of the reference
+            let key = key.as_str().trim_end_matches(['.', ':', '/']);
+            (cap.get(0).unwrap().start(), String::from(key))
+        })
+        .collect();
+    refs.extend(CITE_PATTERN.captures_iter(&content).map(|cap| {
+        let key = cap.name("label").or(cap.name("string")).unwrap();
+        (cap.get(0).unwrap().start(), String::from(key.as_str()))
+    }));
+    refs.sort_by_key(|(pos, _)| *pos);
+    parsed.keys = refs.into_iter().map(|(_, key)| key).collect();
+
+    for cap in BIBLIOGRAPHY_PATTERN.captures_iter(&content) {
+        let args = &cap["args"];
+        parsed.bibliographies.extend(
+            STRING_PATTERN
+                .captures_iter(args)
+                .map(|cap| String::from(&cap["val"]))
+                .filter(|val| val.ends_with(".bib")),
+        );
+        if args.contains("full: true") {
+            parsed.full_bibliography = true;
+        }
+    }
+    parsed.includes = INCLUDE_PATTERN
+        .captures_iter(&content)
+        .map(|cap| String::from(&cap["path"]))
+        .collect();
+    parsed
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn references() {
+        let content = r#"
+#set page(paper: "a4")
+As shown by @DBLP:journals/jair/JabsBNJ24, and others #cite(<DBLP:conf/cp/JabsBIJ23>).
+See @knuth1984[p. 3] and #cite(label("DBLP:conf/sat/DaviesB13"), form: "prose").
+// @DBLP:conf/commented/Out
+/* #cite(<DBLP:conf/block/Out>) */
+Mail me at someone@example.com or follow https://dblp.org.
+`@DBLP:conf/raw/Out`
+#include "chapters/intro.typ"
+#bibliography(("refs.bib", "dblp.bib"), style: "ieee.csl", full: true)
+"#;
+        let parsed = super::parse(content);
+        assert_eq!(
+            parsed.keys,
+            [
+                "DBLP:journals/jair/JabsBNJ24",
+                "DBLP:conf/cp/JabsBIJ23",
+                "knuth1984",
+                "DBLP:conf/sat/DaviesB13",
+            ]
+        );
+        assert_eq!(parsed.bibliographies, ["refs.bib", "dblp.bib"]);
+        assert_eq!(parsed.includes, ["chapters/intro.typ"]);
+        assert!(parsed.full_bibliography);
+    }
+}
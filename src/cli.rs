@@ -1,7 +1,18 @@
+use clap::ArgAction;
 use owo_colors::Style;
 
+pub const DEFAULT_CONCURRENT_REQUESTS: usize = 2;
+pub const DEFAULT_RATE_LIMIT: u64 = 4000;
+pub const DEFAULT_KEY_PREFIX: &str = "DBLP:";
+pub const DEFAULT_BIB_NAME: &str = "dblp";
+
 #[derive(clap::Parser, Clone, Debug)]
 #[command(version, about, long_about = None)]
+#[command(
+    after_help = "Defaults can be set in `$XDG_CONFIG_HOME/dblp-tools/config.toml` and in a \
+`.dblp-tools.toml` next to the document, see `dblp config show`. Flags that are configured can \
+be turned off with, e.g., `--unicode=false`."
+)]
 pub struct Args {
     /// Whether to color the output
     #[arg(long, global = true, default_value = "auto")]
@@ -17,20 +28,21 @@ pub struct Args {
 #[command(next_help_heading = "DBLP server args")]
 pub struct DblpServerArgs {
     /// Use the server at `dblp.uni-trier.de`, rather than `dblp.org`
-    #[arg(short, long, global = true)]
+    #[arg(short, long, global = true, action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_value_t = false, default_missing_value = "true")]
     pub trier: bool,
     /// Use a custom DBLP server
     #[arg(long, global = true, conflicts_with = "trier")]
     pub dblp_domain: Option<String>,
     /// The maximum number of concurrent requests to DBLP to open
-    #[arg(short = 'j', long, global = true, default_value_t = 2)]
+    #[arg(short = 'j', long, global = true, default_value_t = DEFAULT_CONCURRENT_REQUESTS)]
     pub concurrent_requests: usize,
     /// Rate limit: only send one request in this many milliseconds
-    #[arg(short = 'r', long, global = true, default_value_t = 4000)]
+    #[arg(short = 'r', long, global = true, default_value_t = DEFAULT_RATE_LIMIT)]
     pub rate_limit: u64,
 }
 
-#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+#[derive(clap::ValueEnum, Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Color {
     Always,
     Auto,
@@ -128,6 +140,26 @@ pub enum Commands {
     Get(#[command(flatten)] GetArgs),
     /// Fetches all DBLP bibtex entries for a LaTeX file
     GetAll(#[command(flatten)] GetAllArgs),
+    /// Matches the entries of an existing bibliography to DBLP
+    Import(#[command(flatten)] ImportArgs),
+    /// Inspects the configuration
+    #[command(subcommand)]
+    Config(ConfigCommands),
+    /// Inspects the fixers applied to entries
+    #[command(subcommand)]
+    Fixers(FixersCommands),
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum FixersCommands {
+    /// Lists all fixers in the order they are applied
+    List(#[command(flatten)] FixerArgs),
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum ConfigCommands {
+    /// Prints the effective configuration and where each value came from
+    Show,
 }
 
 #[derive(clap::Args, Debug, Clone)]
@@ -142,26 +174,186 @@ pub struct SearchArgs {
     pub hits: Option<u32>,
 }
 
-#[derive(clap::Args, Debug, Clone, Copy)]
+#[derive(clap::Args, Debug, Clone)]
 pub struct CommonGetArgs {
     /// Include unicode characters, rather than converting them to TeX
-    #[arg(short, long)]
+    ///
+    /// TeX commands for special characters in an existing BibTeX file are converted to unicode.
+    #[arg(short, long, action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_value_t = false, default_missing_value = "true")]
     pub unicode: bool,
     /// Whether to use crossref style citations for `incollections` and `inproceedings`
-    #[arg(short, long)]
+    #[arg(short, long, action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_value_t = false, default_missing_value = "true")]
     pub crossref: bool,
     /// Keep the all external fields (DOI and URL), rather than just one
-    #[arg(long)]
+    #[arg(long, action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_value_t = false, default_missing_value = "true")]
     pub all_externals: bool,
     /// Don't expand journal abbreviations
-    #[arg(long)]
+    #[arg(long, action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_value_t = false, default_missing_value = "true")]
     pub dont_expand_journals: bool,
+    /// How to write journal and conference names in `journal` and `booktitle`
+    #[arg(long, value_name = "STYLE", default_value_t, value_enum)]
+    pub venue_style: crate::venues::Style,
+    /// The prefix of DBLP citation keys
+    #[arg(long, default_value = DEFAULT_KEY_PREFIX)]
+    pub key_prefix: String,
+    /// The format to write entries in
+    ///
+    /// biblatex entries use native fields like `date` and `journaltitle`, write CoRR preprints as
+    /// `@online` entries with an arXiv `eprint` and keep both DOI and URL. For CSL-JSON, RIS and
+    /// Hayagriva YAML, the fixers writing LaTeX are skipped.
+    #[arg(long, value_name = "FORMAT", default_value_t, value_enum)]
+    pub output_format: crate::export::Format,
+    /// Annotate authors and editors with their ORCID from DBLP
+    ///
+    /// The ORCIDs are written as biblatex data annotations, e.g., `author+an:orcid`.
+    #[arg(long, action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_value_t = false, default_missing_value = "true")]
+    pub orcid: bool,
+    /// A TOML file with manual corrections of individual entries
+    ///
+    /// The corrections are applied after the built-in corrections of known mistakes in DBLP.
+    #[arg(long)]
+    pub corrections: Option<camino::Utf8PathBuf>,
+    /// A TOML dictionary of words to protect in titles and name overrides, in addition to the
+    /// built-in one
+    #[arg(long = "dictionary")]
+    pub dictionaries: Vec<camino::Utf8PathBuf>,
+    /// Convert titles to this case, protected text is never changed
+    ///
+    /// Formats not read by TeX are left to their consumers to case.
+    #[arg(long, value_name = "CASE")]
+    pub casing: Option<crate::fixers::casing::Case>,
+    /// Show which fixer changed which field of each entry
+    #[arg(long)]
+    pub explain: bool,
+    /// Write the changes of each fixer to this file, as one JSON object per entry and line
+    #[arg(long, value_name = "FILE")]
+    pub explain_log: Option<camino::Utf8PathBuf>,
+    #[command(flatten)]
+    pub fixers: FixerArgs,
+    /// The fixer pipeline built from the arguments
+    #[arg(skip)]
+    pub pipeline: crate::fixers::pipeline::Pipeline,
+}
+
+impl CommonGetArgs {
+    /// Builds the fixer pipeline, loading the corrections and dictionaries used by the fixers
+    pub fn build_pipeline(&mut self) -> color_eyre::Result<()> {
+        use color_eyre::eyre::WrapErr;
+
+        use crate::fixers::{corrections::Corrections, dictionary::Dictionary};
+
+        let mut corrections = Corrections::builtin();
+        if let Some(path) = &self.corrections {
+            corrections.extend(
+                Corrections::read(path)
+                    .wrap_err_with(|| format!("Failed to load corrections from `{path}`"))?,
+            );
+        }
+        let mut dictionary = Dictionary::builtin();
+        for path in &self.dictionaries {
+            dictionary.extend(
+                Dictionary::read(path)
+                    .wrap_err_with(|| format!("Failed to load dictionary from `{path}`"))?,
+            );
+        }
+        let mut pipeline = crate::fixers::pipeline::Pipeline::builtin(
+            dictionary,
+            corrections,
+            self.casing.unwrap_or_default(),
+        );
+        if self.casing.is_some() {
+            pipeline.enable("casing")?;
+        }
+        if self.unicode {
+            pipeline.disable("unicode")?;
+        }
+        if self.all_externals || self.output_format != crate::export::Format::Bibtex {
+            pipeline.disable("single_external")?;
+        }
+        if !self.output_format.is_tex() {
+            for name in crate::export::TEX_ONLY_FIXERS {
+                pipeline.disable(name)?;
+            }
+        }
+        self.fixers.configure(&mut pipeline)?;
+        self.pipeline = pipeline;
+        Ok(())
+    }
+}
+
+/// Arguments selecting the fixers to apply to entries
+#[derive(clap::Args, Debug, Clone, Default)]
+#[command(next_help_heading = "Fixer args")]
+pub struct FixerArgs {
+    /// Enable a fixer that is disabled by default
+    #[arg(long = "enable-fixer", value_name = "FIXER")]
+    pub enable: Vec<String>,
+    /// Disable a fixer, takes precedence over enabling it
+    #[arg(long = "disable-fixer", value_name = "FIXER")]
+    pub disable: Vec<String>,
+    /// Apply the listed fixers in this order, keeping all other fixers in place
+    #[arg(long, value_name = "FIXER", value_delimiter = ',')]
+    pub fixer_order: Vec<String>,
+}
+
+impl FixerArgs {
+    /// Enables, disables and reorders fixers and checks the ordering constraints
+    pub fn configure(
+        &self,
+        pipeline: &mut crate::fixers::pipeline::Pipeline,
+    ) -> Result<(), crate::fixers::pipeline::Error> {
+        for name in &self.enable {
+            pipeline.enable(name)?;
+        }
+        for name in &self.disable {
+            pipeline.disable(name)?;
+        }
+        pipeline.reorder(&self.fixer_order)?;
+        pipeline.check()
+    }
 }
 
 #[derive(clap::Args, Debug, Clone)]
 pub struct GetArgs {
-    /// The DBLP citekey
-    pub key: String,
+    /// The DBLP citekeys
+    ///
+    /// The URL of the record on DBLP, e.g., `https://dblp.org/rec/conf/cp/JabsBIJ23.html`, or a
+    /// DOI are also accepted.
+    #[arg(required_unless_present_any = ["doi", "from_file"])]
+    pub keys: Vec<String>,
+    /// Get the publication with this DOI, bare or as `https://doi.org/` URL
+    #[arg(long, value_name = "DOI")]
+    pub doi: Option<String>,
+    /// Read further keys from this file, one per line, or from stdin if `-`
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    #[arg(long, value_name = "FILE")]
+    pub from_file: Option<camino::Utf8PathBuf>,
+    /// Write the entries to this file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<camino::Utf8PathBuf>,
+    #[command(flatten)]
+    pub common: CommonGetArgs,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ImportArgs {
+    /// The bibliography to import, as BibTeX (`.bib`), CSL-JSON (`.json`) or RIS (`.ris`)
+    ///
+    /// Each entry is searched on DBLP by its DOI, or otherwise by its title and first author. The
+    /// candidates are scored by title similarity, author overlap and year.
+    pub path: camino::Utf8PathBuf,
+    /// The file to write the matched DBLP entries to, stdout if not given
+    pub output: Option<camino::Utf8PathBuf>,
+    /// Write a TOML table from the original citation keys to the matched DBLP keys to this file
+    ///
+    /// The table can be used to rename the citations in the `.tex` sources, or directly as alias
+    /// file for `get-all`.
+    #[arg(long, value_name = "FILE")]
+    pub rename_map: Option<camino::Utf8PathBuf>,
+    /// Only accept matches with at least this confidence
+    #[arg(long, value_name = "CONFIDENCE", default_value_t, value_enum)]
+    pub min_confidence: crate::import::matching::Confidence,
     #[command(flatten)]
     pub common: CommonGetArgs,
 }
@@ -170,22 +362,64 @@ pub struct GetArgs {
 pub struct GetAllArgs {
     /// The LaTeX file to get all DBLP bibtex entries for
     ///
-    /// The tool will read the `.aux` file. Assuming your main file is called `main.tex`, this can
-    /// point to `main.tex`, `main.aux` or `main`.
+    /// The tool will read the `.aux` file, or the biblatex `.bcf` file if one exists. Assuming
+    /// your main file is called `main.tex`, this can point to `main.tex`, `main.aux` or `main`.
+    /// Pandoc Markdown (`.md`) and Typst (`.typ`) documents are scanned directly.
     pub latex_path: camino::Utf8PathBuf,
     /// The optional BibTeX file to write the output to
     ///
-    /// The tool will read the `.aux` file. Assuming your main file is called `main.tex`, this can
-    /// point to `main.tex`, `main.aux` or `main`.
+    /// If not given, the bibliography database named `--bib-name` that the document references
+    /// is used. If the document references no such file, the output is written to stdout.
+    ///
+    /// Formats not read by TeX are written to stdout if no file is given, and never to a BibTeX
+    /// file.
     pub bibtex_path: Option<camino::Utf8PathBuf>,
+    /// A TOML file mapping citation key aliases to DBLP keys
+    ///
+    /// Aliased citations are fetched from DBLP but written under the alias key, with an `ids`
+    /// field holding the DBLP key.
+    #[arg(short, long)]
+    pub aliases: Option<camino::Utf8PathBuf>,
+    /// The name of the BibTeX file dedicated to DBLP entries, without extension
+    #[arg(long, default_value = DEFAULT_BIB_NAME)]
+    pub bib_name: String,
+    /// Write venue names, publishers and series that are repeated across entries as `@string`
+    /// macros
+    #[arg(long, action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_value_t = false, default_missing_value = "true")]
+    pub string_macros: bool,
     #[command(flatten)]
     pub common: CommonGetArgs,
+    /// Scan the `.tex` sources for citations, rather than the `.aux` file
+    ///
+    /// This follows `\input`, `\include` and `\subfile` and can be used before the document has
+    /// been compiled for the first time.
+    #[arg(long)]
+    pub from_tex: bool,
+    /// Only consider citations from this biblatex refsection
+    ///
+    /// Only has an effect when reading citations from a biblatex `.bcf` file.
+    #[arg(long)]
+    pub refsection: Option<u32>,
     /// Don't follow `\@input` commands in the LaTeX aux file
     #[arg(short = 'f', long)]
     pub no_follow_inputs: bool,
     /// Don't reuse existing entries in BibTeX file
     #[arg(short = 'C', long)]
     pub dont_reuse_existing: bool,
+    /// Drop existing entries that are no longer cited from the BibTeX file
+    ///
+    /// Proceedings that are still the crossref target of a cited entry are kept.
+    #[arg(long, conflicts_with = "dont_reuse_existing")]
+    pub prune: bool,
+    /// List existing entries that are no longer cited, without removing them
+    #[arg(long)]
+    pub report_unused: bool,
+    /// Keep running and fetch newly cited entries whenever the document is recompiled
+    #[arg(short, long)]
+    pub watch: bool,
+    /// Time in milliseconds without further changes to wait for before updating in watch mode
+    #[arg(long, default_value_t = 1000, requires = "watch")]
+    pub debounce: u64,
 }
 
 #[cfg(test)]
@@ -0,0 +1,616 @@
+//! # Configuration
+//!
+//! Layered TOML configuration for per-user and per-project defaults. The layers are, in order of
+//! increasing precedence:
+//!
+//! 1. the built-in defaults,
+//! 2. the user configuration at `$XDG_CONFIG_HOME/dblp-tools/config.toml`,
+//! 3. the project configuration at `.dblp-tools.toml` next to the document,
+//! 4. command line arguments.
+//!
+//! Flags that are turned on in a configuration file can be turned off on the command line with,
+//! e.g., `--unicode=false`.
+//!
+//! The keys mirror the long names of the command line arguments:
+//!
+//! ```toml
+//! [server]
+//! trier = true
+//! rate_limit = 2000
+//!
+//! [fixers]
+//! crossref = true
+//!
+//! [output]
+//! bibtex_path = "dblp.bib"
+//! key_prefix = "DBLP:"
+//! ```
+
+use std::fmt;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::parser::ValueSource;
+
+use crate::cli;
+
+const USER_CONFIG: &str = "dblp-tools/config.toml";
+const PROJECT_CONFIG: &str = ".dblp-tools.toml";
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("IO error reading `{0}`: {1}")]
+    Io(Utf8PathBuf, std::io::Error),
+    #[error("Invalid configuration file `{0}`: {1}")]
+    Toml(Utf8PathBuf, toml::de::Error),
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: Server,
+    pub fixers: Fixers,
+    pub output: Output,
+}
+
+/// Settings of the DBLP server, see [`cli::DblpServerArgs`]
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Server {
+    pub trier: Option<bool>,
+    pub dblp_domain: Option<String>,
+    pub concurrent_requests: Option<usize>,
+    pub rate_limit: Option<u64>,
+}
+
+/// Fixer toggles, see [`cli::CommonGetArgs`]
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Fixers {
+    pub unicode: Option<bool>,
+    pub crossref: Option<bool>,
+    pub all_externals: Option<bool>,
+    pub dont_expand_journals: Option<bool>,
+    /// Style of journal and conference names, see [`crate::venues`]
+    pub venue_style: Option<crate::venues::Style>,
+    /// File with manual corrections, see [`crate::fixers::corrections`]
+    pub corrections: Option<Utf8PathBuf>,
+    /// Dictionaries of words to protect in titles, see [`crate::fixers::dictionary`]
+    pub dictionaries: Option<Vec<Utf8PathBuf>>,
+    /// Case to convert titles to
+    pub casing: Option<crate::fixers::casing::Case>,
+    /// Fixers to enable, see `dblp fixers list`
+    pub enable: Option<Vec<String>>,
+    /// Fixers to disable
+    pub disable: Option<Vec<String>>,
+    /// Order to apply fixers in
+    pub order: Option<Vec<String>>,
+}
+
+/// Output settings
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Output {
+    pub color: Option<cli::Color>,
+    pub key_prefix: Option<String>,
+    /// Format of the written entries, see [`crate::export`]
+    pub format: Option<crate::export::Format>,
+    pub orcid: Option<bool>,
+    pub bibtex_path: Option<Utf8PathBuf>,
+    pub bib_name: Option<String>,
+    pub string_macros: Option<bool>,
+    pub aliases: Option<Utf8PathBuf>,
+}
+
+impl Config {
+    /// The built-in defaults
+    pub fn defaults() -> Self {
+        Self {
+            server: Server {
+                trier: Some(false),
+                dblp_domain: None,
+                concurrent_requests: Some(cli::DEFAULT_CONCURRENT_REQUESTS),
+                rate_limit: Some(cli::DEFAULT_RATE_LIMIT),
+            },
+            fixers: Fixers {
+                unicode: Some(false),
+                crossref: Some(false),
+                all_externals: Some(false),
+                dont_expand_journals: Some(false),
+                venue_style: Some(Default::default()),
+                corrections: None,
+                dictionaries: Some(vec![]),
+                casing: None,
+                enable: Some(vec![]),
+                disable: Some(vec![]),
+                order: Some(vec![]),
+            },
+            output: Output {
+                color: Some(cli::Color::Auto),
+                key_prefix: Some(String::from(cli::DEFAULT_KEY_PREFIX)),
+                format: Some(Default::default()),
+                orcid: Some(false),
+                bibtex_path: None,
+                bib_name: Some(String::from(cli::DEFAULT_BIB_NAME)),
+                string_macros: Some(false),
+                aliases: None,
+            },
+        }
+    }
+
+    pub fn read(path: &Utf8Path) -> Result<Self, Error> {
+        let content =
+            std::fs::read_to_string(path).map_err(|err| Error::Io(path.to_path_buf(), err))?;
+        let mut config: Self =
+            toml::from_str(&content).map_err(|err| Error::Toml(path.to_path_buf(), err))?;
+        // paths in configuration files are relative to the file
+        let dir = path.parent().unwrap_or(Utf8Path::new(""));
+        for path in [
+            &mut config.output.bibtex_path,
+            &mut config.output.aliases,
+            &mut config.fixers.corrections,
+        ]
+        .into_iter()
+        .flatten()
+        .chain(config.fixers.dictionaries.iter_mut().flatten())
+        {
+            *path = dir.join(&*path);
+        }
+        Ok(config)
+    }
+
+    /// The server arguments given on the command line
+    fn from_command_line(args: &cli::Args, matches: &clap::ArgMatches) -> Self {
+        let given = |id| from_command_line(matches, id);
+        let mut config = Self::default();
+        if given("trier") {
+            config.server.trier = Some(args.dblp.trier);
+        }
+        if given("dblp_domain") {
+            config.server.dblp_domain = args.dblp.dblp_domain.clone();
+        }
+        if given("concurrent_requests") {
+            config.server.concurrent_requests = Some(args.dblp.concurrent_requests);
+        }
+        if given("rate_limit") {
+            config.server.rate_limit = Some(args.dblp.rate_limit);
+        }
+        if given("color") {
+            config.output.color = Some(args.color);
+        }
+        config
+    }
+}
+
+/// Where a configuration value came from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(Utf8PathBuf),
+    CommandLine,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "{path}"),
+            Source::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+/// The stack of configuration layers
+#[derive(Clone, Debug)]
+pub struct Layered {
+    layers: Vec<(Source, Config)>,
+}
+
+/// The path of the user configuration file
+fn user_config() -> Option<Utf8PathBuf> {
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        return Some(
+            Utf8PathBuf::try_from(std::path::PathBuf::from(dir))
+                .ok()?
+                .join(USER_CONFIG),
+        );
+    }
+    let home = Utf8PathBuf::try_from(std::path::PathBuf::from(std::env::var_os("HOME")?)).ok()?;
+    Some(home.join(".config").join(USER_CONFIG))
+}
+
+/// The directory of the document the command works on, where the project configuration lives
+fn project_dir(args: &cli::Args) -> Utf8PathBuf {
+    match &args.command {
+        cli::Commands::GetAll(get_all) => get_all
+            .latex_path
+            .parent()
+            .filter(|dir| !dir.as_str().is_empty())
+            .map_or_else(|| Utf8PathBuf::from("."), Utf8Path::to_path_buf),
+        _ => Utf8PathBuf::from("."),
+    }
+}
+
+/// Checks whether an argument was given on the command line to the command or its subcommands
+fn from_command_line(matches: &clap::ArgMatches, id: &str) -> bool {
+    let mut matches = Some(matches);
+    while let Some(current) = matches {
+        if current.ids().any(|known| known.as_str() == id)
+            && current.value_source(id) == Some(ValueSource::CommandLine)
+        {
+            return true;
+        }
+        matches = current.subcommand().map(|(_, sub)| sub);
+    }
+    false
+}
+
+/// Overwrites `$target` with the configured value, unless the argument was given on the command
+/// line
+macro_rules! apply {
+    ($matches:expr, $id:literal, $target:expr, $value:expr) => {
+        if !from_command_line($matches, $id)
+            && let Some(value) = $value
+        {
+            $target = value.clone();
+        }
+    };
+}
+
+impl Layered {
+    /// Loads the configuration files relevant for the command
+    pub fn load(args: &cli::Args, matches: &clap::ArgMatches) -> Result<Self, Error> {
+        let mut layers = vec![(Source::Default, Config::defaults())];
+        let user = user_config();
+        let project = project_dir(args).join(PROJECT_CONFIG);
+        for path in user.into_iter().chain([project]) {
+            if path.is_file() {
+                let config = Config::read(&path)?;
+                layers.push((Source::File(path), config));
+            }
+        }
+        layers.push((
+            Source::CommandLine,
+            Config::from_command_line(args, matches),
+        ));
+        Ok(Self { layers })
+    }
+
+    /// Gets the effective value of a setting and where it came from
+    pub fn get<T, F>(&self, field: F) -> Option<(&T, &Source)>
+    where
+        F: Fn(&Config) -> &Option<T>,
+    {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|(source, config)| field(config).as_ref().map(|val| (val, source)))
+    }
+
+    fn value<T, F>(&self, field: F) -> Option<&T>
+    where
+        F: Fn(&Config) -> &Option<T>,
+    {
+        self.get(field).map(|(val, _)| val)
+    }
+
+    /// Applies the configuration to arguments that were not given on the command line
+    pub fn apply(&self, args: &mut cli::Args, matches: &clap::ArgMatches) {
+        apply!(
+            matches,
+            "color",
+            args.color,
+            self.value(|c| &c.output.color)
+        );
+        // the server given on the command line overrides any configured server
+        if !from_command_line(matches, "trier") && !from_command_line(matches, "dblp_domain") {
+            args.dblp.trier = self.value(|c| &c.server.trier).copied().unwrap_or_default();
+            args.dblp.dblp_domain = self.value(|c| &c.server.dblp_domain).cloned();
+        }
+        apply!(
+            matches,
+            "concurrent_requests",
+            args.dblp.concurrent_requests,
+            self.value(|c| &c.server.concurrent_requests)
+        );
+        apply!(
+            matches,
+            "rate_limit",
+            args.dblp.rate_limit,
+            self.value(|c| &c.server.rate_limit)
+        );
+
+        let common = match &mut args.command {
+            cli::Commands::Fixers(cli::FixersCommands::List(fixers)) => {
+                self.apply_fixers(fixers, matches);
+                return;
+            }
+            cli::Commands::Get(get) => &mut get.common,
+            cli::Commands::Import(import) => &mut import.common,
+            cli::Commands::GetAll(get_all) => {
+                if !from_command_line(matches, "bibtex_path") {
+                    get_all.bibtex_path = self.value(|c| &c.output.bibtex_path).cloned();
+                }
+                apply!(
+                    matches,
+                    "bib_name",
+                    get_all.bib_name,
+                    self.value(|c| &c.output.bib_name)
+                );
+                apply!(
+                    matches,
+                    "string_macros",
+                    get_all.string_macros,
+                    self.value(|c| &c.output.string_macros)
+                );
+                if !from_command_line(matches, "aliases") {
+                    get_all.aliases = self.value(|c| &c.output.aliases).cloned();
+                }
+                &mut get_all.common
+            }
+            _ => return,
+        };
+        apply!(
+            matches,
+            "unicode",
+            common.unicode,
+            self.value(|c| &c.fixers.unicode)
+        );
+        apply!(
+            matches,
+            "crossref",
+            common.crossref,
+            self.value(|c| &c.fixers.crossref)
+        );
+        apply!(
+            matches,
+            "all_externals",
+            common.all_externals,
+            self.value(|c| &c.fixers.all_externals)
+        );
+        apply!(
+            matches,
+            "dont_expand_journals",
+            common.dont_expand_journals,
+            self.value(|c| &c.fixers.dont_expand_journals)
+        );
+        apply!(
+            matches,
+            "venue_style",
+            common.venue_style,
+            self.value(|c| &c.fixers.venue_style)
+        );
+        apply!(
+            matches,
+            "dictionaries",
+            common.dictionaries,
+            self.value(|c| &c.fixers.dictionaries)
+        );
+        if !from_command_line(matches, "corrections") {
+            common.corrections = self.value(|c| &c.fixers.corrections).cloned();
+        }
+        apply!(
+            matches,
+            "key_prefix",
+            common.key_prefix,
+            self.value(|c| &c.output.key_prefix)
+        );
+        apply!(
+            matches,
+            "output_format",
+            common.output_format,
+            self.value(|c| &c.output.format)
+        );
+        apply!(
+            matches,
+            "orcid",
+            common.orcid,
+            self.value(|c| &c.output.orcid)
+        );
+        if !from_command_line(matches, "casing") {
+            common.casing = self.value(|c| &c.fixers.casing).copied();
+        }
+        self.apply_fixers(&mut common.fixers, matches);
+    }
+
+    fn apply_fixers(&self, args: &mut cli::FixerArgs, matches: &clap::ArgMatches) {
+        apply!(
+            matches,
+            "enable",
+            args.enable,
+            self.value(|c| &c.fixers.enable)
+        );
+        apply!(
+            matches,
+            "disable",
+            args.disable,
+            self.value(|c| &c.fixers.disable)
+        );
+        apply!(
+            matches,
+            "fixer_order",
+            args.fixer_order,
+            self.value(|c| &c.fixers.order)
+        );
+    }
+
+    /// Displays the effective configuration with the source of each value
+    pub fn display(&self) -> LayeredDisplay<'_> {
+        LayeredDisplay { value: self }
+    }
+}
+
+/// A configuration value with its source
+type Entry<'a> = (String, toml::Value, &'a Source);
+
+/// Displayer for [`Layered`]
+pub struct LayeredDisplay<'a> {
+    value: &'a Layered,
+}
+
+impl fmt::Display for LayeredDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // serialize each layer to find the effective value and source of each key
+        let mut sections: Vec<(String, Vec<Entry>)> = vec![];
+        for (source, config) in &self.value.layers {
+            let toml::Value::Table(table) =
+                toml::Value::try_from(config).expect("config must be serializable")
+            else {
+                unreachable!("config serializes to a table");
+            };
+            for (section, values) in table {
+                let toml::Value::Table(values) = values else {
+                    continue;
+                };
+                let idx = match sections.iter().position(|(name, _)| *name == section) {
+                    Some(idx) => idx,
+                    None => {
+                        sections.push((section, vec![]));
+                        sections.len() - 1
+                    }
+                };
+                let entries = &mut sections[idx].1;
+                for (key, value) in values {
+                    match entries.iter_mut().find(|(known, _, _)| *known == key) {
+                        Some(entry) => *entry = (key, value, source),
+                        None => entries.push((key, value, source)),
+                    }
+                }
+            }
+        }
+        for (idx, (section, entries)) in sections.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "[{section}]")?;
+            for (key, value, source) in entries {
+                writeln!(f, "{key} = {value} # {source}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::{Config, Layered, Source};
+
+    fn parse(argv: &[&str]) -> (crate::cli::Args, clap::ArgMatches) {
+        let matches = crate::cli::Args::command().get_matches_from(argv);
+        let args = crate::cli::Args::from_arg_matches(&matches).unwrap();
+        (args, matches)
+    }
+
+    #[test]
+    fn layering() {
+        let user: Config = toml::from_str(
+            r#"
+[server]
+rate_limit = 1000
+trier = true
+
+[fixers]
+crossref = true
+"#,
+        )
+        .unwrap();
+        let project: Config = toml::from_str(
+            r#"
+[server]
+rate_limit = 2000
+
+[output]
+key_prefix = "dblp:"
+"#,
+        )
+        .unwrap();
+        let (mut args, matches) = parse(&["dblp", "get", "-r", "3000", "DBLP:conf/cp/JabsBIJ23"]);
+        let layered = Layered {
+            layers: vec![
+                (Source::Default, Config::defaults()),
+                (Source::File("user.toml".into()), user),
+                (Source::File("project.toml".into()), project),
+                (
+                    Source::CommandLine,
+                    Config::from_command_line(&args, &matches),
+                ),
+            ],
+        };
+        layered.apply(&mut args, &matches);
+
+        assert_eq!(args.dblp.rate_limit, 3000);
+        assert!(args.dblp.trier);
+        assert_eq!(
+            args.dblp.concurrent_requests,
+            crate::cli::DEFAULT_CONCURRENT_REQUESTS
+        );
+        let crate::cli::Commands::Get(get) = args.command else {
+            panic!("parsed wrong command");
+        };
+        assert!(get.common.crossref);
+        assert!(!get.common.unicode);
+        assert_eq!(get.common.key_prefix, "dblp:");
+
+        assert_eq!(
+            layered.get(|c| &c.server.rate_limit),
+            Some((&3000, &Source::CommandLine))
+        );
+        assert_eq!(
+            layered.get(|c| &c.server.trier),
+            Some((&true, &Source::File("user.toml".into())))
+        );
+        assert_eq!(
+            layered.get(|c| &c.output.key_prefix).map(|(_, src)| src),
+            Some(&Source::File("project.toml".into()))
+        );
+
+        let shown = format!("{}", layered.display());
+        assert!(shown.contains("rate_limit = 3000 # command line"));
+        assert!(shown.contains("crossref = true # user.toml"));
+        assert!(shown.contains("unicode = false # default"));
+    }
+
+    #[test]
+    fn flags_off() {
+        let project: Config = toml::from_str(
+            r#"
+[fixers]
+unicode = true
+
+[output]
+orcid = true
+"#,
+        )
+        .unwrap();
+        let (mut args, matches) = parse(&["dblp", "get", "--unicode=false", "conf/cp/JabsBIJ23"]);
+        let layered = Layered {
+            layers: vec![
+                (Source::Default, Config::defaults()),
+                (Source::File("project.toml".into()), project),
+                (
+                    Source::CommandLine,
+                    Config::from_command_line(&args, &matches),
+                ),
+            ],
+        };
+        layered.apply(&mut args, &matches);
+
+        let crate::cli::Commands::Get(get) = args.command else {
+            panic!("parsed wrong command");
+        };
+        assert!(!get.common.unicode);
+        assert!(get.common.orcid);
+
+        let (args, _) = parse(&["dblp", "get", "-u", "conf/cp/JabsBIJ23"]);
+        let crate::cli::Commands::Get(get) = args.command else {
+            panic!("parsed wrong command");
+        };
+        assert!(get.common.unicode);
+    }
+
+    #[test]
+    fn unknown_keys() {
+        assert!(toml::from_str::<Config>("[server]\nunknown = 1\n").is_err());
+    }
+}
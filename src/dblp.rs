@@ -1,7 +1,9 @@
+mod key;
 pub mod record;
 pub mod search;
 mod stream;
 
+pub use key::{DblpKey, InvalidKey};
 pub use record::Record;
 
 fn domain(opts: &super::cli::DblpServerArgs) -> &str {
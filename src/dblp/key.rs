@@ -0,0 +1,114 @@
+//! # DBLP Keys
+//!
+//! Records on DBLP are identified by keys of the shape `type/venue/id`, e.g.,
+//! `conf/cp/JabsBIJ23`. Keys are accepted in all the forms they are commonly pasted in: with the
+//! `DBLP:` prefix DBLP uses in its BibTeX, or as URL of the record's page or one of its exports,
+//! e.g., `https://dblp.org/rec/conf/cp/JabsBIJ23.html` or
+//! `https://dblp.org/rec/conf/cp/JabsBIJ23?view=bibtex`.
+
+use std::{fmt, str::FromStr};
+
+/// The prefix DBLP puts in front of keys in its BibTeX
+const PREFIX: &str = "DBLP:";
+
+/// Extensions of the pages and exports of a record
+const EXTENSIONS: [&str; 3] = [".html", ".bib", ".xml"];
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("`{0}` is not a DBLP key, expected the shape `type/venue/id`")]
+pub struct InvalidKey(pub String);
+
+/// A validated DBLP key, without prefix
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DblpKey(String);
+
+impl DblpKey {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The key of the venue stream of the record, e.g., `cp` for `conf/cp/JabsBIJ23`
+    pub fn venue(&self) -> &str {
+        super::record::venue_key(&self.0)
+    }
+}
+
+/// Strips the URL of a record on DBLP, leaving the key
+fn strip_url(value: &str) -> &str {
+    let Some(path) = ["https://", "http://"]
+        .iter()
+        .find_map(|scheme| value.strip_prefix(scheme))
+        .and_then(|rest| rest.split_once("/rec/"))
+        .map(|(_, path)| path)
+    else {
+        return value;
+    };
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    EXTENSIONS
+        .iter()
+        .find_map(|ext| path.strip_suffix(ext))
+        .unwrap_or(path)
+}
+
+impl FromStr for DblpKey {
+    type Err = InvalidKey;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let trimmed = value.trim();
+        let key = trimmed.strip_prefix(PREFIX).unwrap_or(trimmed);
+        let key = strip_url(key);
+        let valid = key.split('/').count() >= 3
+            && key.split('/').all(|part| {
+                !part.is_empty()
+                    && part
+                        .chars()
+                        .all(|ch| !ch.is_whitespace() && !matches!(ch, ':' | '?' | '#'))
+            });
+        if !valid {
+            return Err(InvalidKey(String::from(value)));
+        }
+        Ok(Self(String::from(key)))
+    }
+}
+
+impl fmt::Display for DblpKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DblpKey, InvalidKey};
+
+    #[test]
+    fn parse() {
+        for value in [
+            "conf/cp/JabsBIJ23",
+            "DBLP:conf/cp/JabsBIJ23",
+            " conf/cp/JabsBIJ23\n",
+            "https://dblp.org/rec/conf/cp/JabsBIJ23.html",
+            "https://dblp.org/rec/conf/cp/JabsBIJ23.bib",
+            "https://dblp.org/rec/conf/cp/JabsBIJ23.xml",
+            "https://dblp.org/rec/conf/cp/JabsBIJ23?view=bibtex",
+            "http://dblp.uni-trier.de/rec/conf/cp/JabsBIJ23",
+        ] {
+            let key: DblpKey = value.parse().unwrap();
+            assert_eq!(key.as_str(), "conf/cp/JabsBIJ23", "parsing `{value}`");
+        }
+        let key: DblpKey = "journals/corr/abs-2403-01234".parse().unwrap();
+        assert_eq!(key.venue(), "corr");
+
+        for value in [
+            "JabsBIJ23",
+            "conf/cp",
+            "conf//JabsBIJ23",
+            "conf/cp/Jabs BIJ23",
+        ] {
+            assert_eq!(
+                value.parse::<DblpKey>(),
+                Err(InvalidKey(String::from(value)))
+            );
+        }
+    }
+}
@@ -3,10 +3,19 @@ use std::fmt;
 use owo_colors::OwoColorize;
 use tower::ServiceExt;
 
-use crate::cli::Styles;
+use crate::{
+    bibtex::strings::Strings,
+    cli::Styles,
+    venues::{self, Style as VenueStyle},
+};
 
 const BASE: &str = "/rec/";
 
+/// The key of the venue stream of a record, e.g., `cp` for `conf/cp/JabsBIJ23`
+pub fn venue_key(key: &str) -> &str {
+    key.split('/').nth(1).unwrap_or_default()
+}
+
 fn query_url(key: &str, opts: &crate::cli::DblpServerArgs) -> reqwest::Url {
     reqwest::Url::parse(&format!("{}{BASE}{key}.xml", super::domain(opts)))
         .expect("should be a proper URL")
@@ -22,6 +31,8 @@ pub enum Error {
     Http(reqwest::StatusCode),
     #[error("DBLP key `{0}` is unknown")]
     UnknownKey(String),
+    #[error(transparent)]
+    InvalidKey(#[from] super::InvalidKey),
     #[error("Service error: {0}")]
     Generic(#[from] Box<dyn std::error::Error + Send + std::marker::Sync + 'static>),
 }
@@ -30,7 +41,7 @@ pub enum Error {
 pub enum Record {
     Article {
         key: String,
-        author: Vec<String>,
+        author: Vec<Person>,
         title: String,
         journal: String,
         year: u32,
@@ -40,7 +51,7 @@ pub enum Record {
     },
     Proceedings {
         key: String,
-        editor: Vec<String>,
+        editor: Vec<Person>,
         title: String,
         year: u32,
         series: Option<String>,
@@ -51,7 +62,7 @@ pub enum Record {
     },
     Inproceedings {
         key: String,
-        author: Vec<String>,
+        author: Vec<Person>,
         title: String,
         booktitle: String,
         year: u32,
@@ -62,8 +73,8 @@ pub enum Record {
     },
     Book {
         key: String,
-        author: Vec<String>,
-        editor: Vec<String>,
+        author: Vec<Person>,
+        editor: Vec<Person>,
         title: String,
         publisher: Option<String>,
         year: u32,
@@ -74,7 +85,7 @@ pub enum Record {
     },
     Incollection {
         key: String,
-        author: Vec<String>,
+        author: Vec<Person>,
         title: String,
         booktitle: String,
         year: u32,
@@ -84,7 +95,7 @@ pub enum Record {
     },
     Misc {
         key: String,
-        author: Vec<String>,
+        author: Vec<Person>,
         title: String,
         year: u32,
         publisher: Option<String>,
@@ -96,28 +107,60 @@ pub enum Record {
 pub enum Crossref {
     Key(String),
     Resolved {
-        editor: Vec<String>,
+        editor: Vec<Person>,
         publisher: Option<String>,
         series: Option<String>,
         volume: Option<String>,
     },
 }
 
-impl Record {
-    pub async fn get(
-        key: &str,
-        resolve_crossref: bool,
-        expand_journal: bool,
-        opts: &crate::cli::DblpServerArgs,
-    ) -> Result<Self, Error> {
-        let mut service = super::new_service(opts);
-        Self::get_with_service(key, resolve_crossref, expand_journal, opts, &mut service).await
+/// An author or editor
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+pub struct Person {
+    /// The name as displayed by DBLP, e.g., `João Marques-Silva 0001`
+    #[serde(rename = "$text")]
+    pub name: String,
+    /// The DBLP person identifier, e.g., `19/3985`
+    #[serde(rename = "@pid", default)]
+    pub pid: Option<String>,
+    #[serde(rename = "@orcid", default)]
+    pub orcid: Option<String>,
+    /// The DBLP disambiguation suffix, e.g., `0001`, once stripped from the name
+    #[serde(skip)]
+    pub number: Option<String>,
+}
+
+impl From<String> for Person {
+    fn from(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
     }
+}
+
+impl From<&str> for Person {
+    fn from(name: &str) -> Self {
+        Self::from(String::from(name))
+    }
+}
+
+impl fmt::Display for Person {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
 
+impl Record {
+    /// Fetches a record from DBLP
+    ///
+    /// Journal names are only styled according to `venue_style` if `expand_journal` is set,
+    /// otherwise DBLP's short name is kept.
     pub async fn get_with_service<Service>(
         key: &str,
         resolve_crossref: bool,
         expand_journal: bool,
+        venue_style: VenueStyle,
         opts: &crate::cli::DblpServerArgs,
         service: &mut Service,
     ) -> Result<Self, Error>
@@ -128,7 +171,8 @@ impl Record {
                 Error = Box<dyn std::error::Error + Send + std::marker::Sync + 'static>,
             >,
     {
-        let key = key.strip_prefix("DBLP:").unwrap_or(key);
+        let dblp_key: super::DblpKey = key.parse()?;
+        let key = dblp_key.as_str();
         let response = service
             .ready()
             .await?
@@ -161,30 +205,22 @@ impl Record {
                 journal,
                 ee,
             } => {
-                if expand_journal {
-                    let journal_key = key.split_once('/').unwrap().1.split_once('/').unwrap().0;
-                    let journal = super::stream::journal_title(journal_key, opts, service).await?;
-                    Self::Article {
-                        key: key.to_string(),
-                        author,
-                        title,
-                        pages,
-                        year,
-                        volume,
-                        journal,
-                        external: ee.into_iter().map(External::from).collect(),
-                    }
+                let journal_key = dblp_key.venue();
+                let journal = if expand_journal && venue_style != VenueStyle::Short {
+                    let stream = super::stream::journal(journal_key, opts, service).await?;
+                    venues::journal(venue_style, journal_key, &journal, Some(&stream))
                 } else {
-                    Self::Article {
-                        key: key.to_string(),
-                        author,
-                        title,
-                        pages,
-                        year,
-                        volume,
-                        journal,
-                        external: ee.into_iter().map(External::from).collect(),
-                    }
+                    journal
+                };
+                Self::Article {
+                    key: key.to_string(),
+                    author,
+                    title,
+                    pages,
+                    year,
+                    volume,
+                    journal,
+                    external: ee.into_iter().map(External::from).collect(),
                 }
             }
             Data::Inproceedings {
@@ -215,7 +251,7 @@ impl Record {
                     }
                     let Data::Proceedings {
                         editor,
-                        title: booktitle,
+                        title: proceedings_title,
                         series,
                         volume,
                         publisher,
@@ -224,6 +260,13 @@ impl Record {
                     else {
                         panic!("crossref data does not match");
                     };
+                    let booktitle = venues::booktitle(
+                        venue_style,
+                        venue_key(key),
+                        &booktitle,
+                        &proceedings_title,
+                        year,
+                    );
                     Self::Inproceedings {
                         key: key.to_string(),
                         author,
@@ -282,7 +325,7 @@ impl Record {
                     }
                     let Data::Book {
                         editor,
-                        title: booktitle,
+                        title: proceedings_title,
                         series,
                         volume,
                         publisher,
@@ -291,6 +334,13 @@ impl Record {
                     else {
                         panic!("crossref data does not match");
                     };
+                    let booktitle = venues::booktitle(
+                        venue_style,
+                        venue_key(key),
+                        &booktitle,
+                        &proceedings_title,
+                        year,
+                    );
                     Self::Incollection {
                         key: key.to_string(),
                         author,
@@ -383,6 +433,11 @@ impl Record {
         Bibtex {
             value: self,
             styles: Box::default(),
+            prefix: "DBLP:",
+            alias: None,
+            orcid: false,
+            strings: None,
+            flavor: Flavor::default(),
         }
     }
 
@@ -422,6 +477,196 @@ impl Record {
             | Record::Misc { title, .. } => title,
         }
     }
+
+    /// The BibTeX fields of the record, in the order they are written
+    ///
+    /// This is the single list of fields shared by [`Bibtex`] and [`Record::fields`].
+    fn field_table(&self) -> Vec<(&'static str, Field<'_>)> {
+        fn optional<'a>(
+            fields: &mut Vec<(&'static str, Field<'a>)>,
+            name: &'static str,
+            value: &'a Option<String>,
+            field: fn(&'a str) -> Field<'a>,
+        ) {
+            if let Some(value) = value {
+                fields.push((name, field(value)));
+            }
+        }
+        fn crossref<'a>(fields: &mut Vec<(&'static str, Field<'a>)>, crossref: &'a Crossref) {
+            match crossref {
+                Crossref::Key(key) => fields.push(("crossref", Field::Crossref(key))),
+                Crossref::Resolved {
+                    editor,
+                    publisher,
+                    series,
+                    volume,
+                } => {
+                    fields.push(("editor", Field::People(editor)));
+                    optional(fields, "series", series, Field::Macro);
+                    optional(fields, "volume", volume, Field::Text);
+                    optional(fields, "publisher", publisher, Field::Macro);
+                }
+            }
+        }
+
+        let mut fields = vec![];
+        match self {
+            Record::Article {
+                author,
+                title,
+                journal,
+                year,
+                pages,
+                volume,
+                external,
+                ..
+            } => {
+                fields.push(("author", Field::People(author)));
+                fields.push(("title", Field::Text(title)));
+                fields.push(("journal", Field::Macro(journal)));
+                fields.push(("year", Field::Year(*year)));
+                optional(&mut fields, "pages", pages, Field::Text);
+                optional(&mut fields, "volume", volume, Field::Text);
+                fields.push(("external", Field::Externals(external)));
+            }
+            Record::Proceedings {
+                editor,
+                title,
+                year,
+                series,
+                volume,
+                publisher,
+                external,
+                isbn,
+                ..
+            } => {
+                fields.push(("editor", Field::People(editor)));
+                fields.push(("title", Field::Text(title)));
+                fields.push(("year", Field::Year(*year)));
+                optional(&mut fields, "series", series, Field::Macro);
+                optional(&mut fields, "volume", volume, Field::Text);
+                optional(&mut fields, "publisher", publisher, Field::Macro);
+                fields.push(("isbn", Field::Isbn(isbn)));
+                fields.push(("external", Field::Externals(external)));
+            }
+            Record::Inproceedings {
+                author,
+                title,
+                booktitle,
+                year,
+                pages,
+                external,
+                crossref: cross,
+                usera,
+                ..
+            } => {
+                fields.push(("author", Field::People(author)));
+                fields.push(("title", Field::Text(title)));
+                fields.push(("booktitle", Field::Macro(booktitle)));
+                fields.push(("year", Field::Year(*year)));
+                optional(&mut fields, "pages", pages, Field::Text);
+                fields.push(("external", Field::Externals(external)));
+                crossref(&mut fields, cross);
+                optional(&mut fields, "usera", usera, Field::Text);
+            }
+            Record::Book {
+                author,
+                editor,
+                title,
+                publisher,
+                year,
+                series,
+                volume,
+                external,
+                isbn,
+                ..
+            } => {
+                fields.push(("author", Field::People(author)));
+                fields.push(("editor", Field::People(editor)));
+                fields.push(("title", Field::Text(title)));
+                optional(&mut fields, "publisher", publisher, Field::Macro);
+                fields.push(("year", Field::Year(*year)));
+                optional(&mut fields, "series", series, Field::Macro);
+                optional(&mut fields, "volume", volume, Field::Text);
+                fields.push(("isbn", Field::Isbn(isbn)));
+                fields.push(("external", Field::Externals(external)));
+            }
+            Record::Incollection {
+                author,
+                title,
+                booktitle,
+                year,
+                pages,
+                external,
+                crossref: cross,
+                ..
+            } => {
+                fields.push(("author", Field::People(author)));
+                fields.push(("title", Field::Text(title)));
+                fields.push(("booktitle", Field::Macro(booktitle)));
+                fields.push(("year", Field::Year(*year)));
+                optional(&mut fields, "pages", pages, Field::Text);
+                fields.push(("external", Field::Externals(external)));
+                crossref(&mut fields, cross);
+            }
+            Record::Misc {
+                author,
+                title,
+                year,
+                publisher,
+                external,
+                ..
+            } => {
+                fields.push(("author", Field::People(author)));
+                fields.push(("title", Field::Text(title)));
+                optional(&mut fields, "publisher", publisher, Field::Macro);
+                fields.push(("year", Field::Year(*year)));
+                fields.push(("external", Field::Externals(external)));
+            }
+        }
+        fields
+    }
+
+    /// All fields of the record with their BibTeX names, people are joined with `and`
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![];
+        for (name, field) in self.field_table() {
+            match field {
+                Field::Text(value) | Field::Macro(value) | Field::Crossref(value) => {
+                    fields.push((name, String::from(value)));
+                }
+                Field::Year(year) => fields.push((name, year.to_string())),
+                Field::People([]) => {}
+                Field::People(people) => {
+                    let names: Vec<_> = people.iter().map(|person| person.name.as_str()).collect();
+                    fields.push((name, names.join(" and ")));
+                }
+                Field::Isbn(isbn) => fields.extend(isbn.iter().map(|isbn| (name, isbn.clone()))),
+                Field::Externals(external) => {
+                    fields.extend(external.iter().map(|external| match external {
+                        External::Url(url) => ("url", url.clone()),
+                        External::Doi(doi) => ("doi", doi.clone()),
+                    }));
+                }
+            }
+        }
+        fields
+    }
+}
+
+/// The value of a BibTeX field, see [`Record::field_table`]
+#[derive(Clone, Copy, Debug)]
+enum Field<'a> {
+    Text(&'a str),
+    /// Venue names, publishers and series, which may be written as `@string` macro
+    Macro(&'a str),
+    Year(u32),
+    People(&'a [Person]),
+    Isbn(&'a [String]),
+    /// DOIs and URLs, each written as its own field
+    Externals(&'a [External]),
+    /// The key of the crossrefed entry, without prefix
+    Crossref(&'a str),
 }
 
 #[derive(Clone, Debug)]
@@ -430,47 +675,233 @@ pub enum External {
     Doi(String),
 }
 
+/// Prefixes of a DOI, either as resolver URL or as `doi:` URI
+const DOI_PREFIXES: [&str; 5] = [
+    "https://doi.org/",
+    "http://doi.org/",
+    "https://dx.doi.org/",
+    "http://dx.doi.org/",
+    "doi:",
+];
+
 impl From<String> for External {
+    /// Recognizes DOIs, given with one of the [`DOI_PREFIXES`] or bare as `10.<registrant>/...`
     fn from(mut value: String) -> Self {
-        if value.starts_with("https://doi.org/") {
-            value.drain(..16);
+        if let Some(prefix) = DOI_PREFIXES
+            .iter()
+            .find(|prefix| value.starts_with(*prefix))
+        {
+            value.drain(..prefix.len());
+            return External::Doi(value);
+        }
+        if value.starts_with("10.") && value.contains('/') {
             return External::Doi(value);
         }
         External::Url(value)
     }
 }
 
+/// Fields that biblatex names differently than BibTeX
+const BIBLATEX_FIELDS: [(&str, &str); 2] = [("journal", "journaltitle"), ("year", "date")];
+
+/// Entry types that biblatex names differently than BibTeX
+const BIBLATEX_TYPES: [(&str, &str); 1] = [("misc", "dataset")];
+
+/// The dialect that [`Bibtex`] writes entries in
+#[derive(
+    clap::ValueEnum,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Flavor {
+    /// Classic BibTeX, e.g., `journal` and `year` fields
+    #[default]
+    Bibtex,
+    /// Native biblatex, e.g., `journaltitle` and `date` fields and `@online` for arXiv preprints
+    Biblatex,
+}
+
+impl Flavor {
+    fn rename<'a>(self, name: &'a str, table: &[(&str, &'static str)]) -> &'a str {
+        match self {
+            Flavor::Bibtex => name,
+            Flavor::Biblatex => table
+                .iter()
+                .find(|(bibtex, _)| *bibtex == name)
+                .map_or(name, |(_, biblatex)| biblatex),
+        }
+    }
+
+    /// The name of a field in this flavor, given its BibTeX name
+    fn field(self, name: &str) -> &str {
+        self.rename(name, &BIBLATEX_FIELDS)
+    }
+
+    /// The name of an entry type in this flavor, given its BibTeX name
+    fn entry_type(self, name: &str) -> &str {
+        self.rename(name, &BIBLATEX_TYPES)
+    }
+}
+
 /// Bibtex displayer for [`Record`]
 pub struct Bibtex<'a> {
     value: &'a Record,
     styles: Box<Styles>,
+    prefix: &'a str,
+    alias: Option<&'a str>,
+    orcid: bool,
+    strings: Option<&'a Strings>,
+    flavor: Flavor,
 }
 
-impl Bibtex<'_> {
+impl<'a> Bibtex<'a> {
     /// Colorizes the output
     pub fn colorize(&mut self) {
         self.styles.colorize();
     }
-}
 
-fn bibtex_start(
-    f: &mut fmt::Formatter<'_>,
-    bibtype: &str,
-    key: &str,
-    styles: &Styles,
-) -> fmt::Result {
-    writeln!(
-        f,
-        "@{bibtype}{{DBLP:{key},",
-        bibtype = bibtype.style(styles.bibtex_type),
-        key = key.style(styles.citekey)
-    )
+    /// Sets the prefix of DBLP citation keys, `DBLP:` by default
+    pub fn prefix(&mut self, prefix: &'a str) -> &mut Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Writes the entry under an alias key, with the DBLP key in the `ids` field
+    pub fn alias(&mut self, alias: &'a str) -> &mut Self {
+        self.alias = Some(alias);
+        self
+    }
+
+    /// Annotates people with their ORCID in biblatex `author+an:orcid` fields
+    pub fn orcid(&mut self, orcid: bool) -> &mut Self {
+        self.orcid = orcid;
+        self
+    }
+
+    /// References values defined as `@string` macros by name
+    pub fn strings(&mut self, strings: &'a Strings) -> &mut Self {
+        self.strings = Some(strings);
+        self
+    }
+
+    /// Writes the entry in this flavor
+    pub fn flavor(&mut self, flavor: Flavor) -> &mut Self {
+        self.flavor = flavor;
+        self
+    }
+
+    /// Writes a field, renamed for the flavor
+    fn kv<V: fmt::Display>(&self, f: &mut fmt::Formatter<'_>, key: &str, val: &V) -> fmt::Result {
+        bibtex_kv(f, self.flavor.field(key), val, &self.styles)
+    }
+
+    /// Writes a field that may be replaced by a string macro
+    fn macro_kv(&self, f: &mut fmt::Formatter<'_>, key: &str, val: &str) -> fmt::Result {
+        let key = self.flavor.field(key);
+        match self.strings.and_then(|strings| strings.get(val)) {
+            Some(name) => writeln!(
+                f,
+                "  {key: <12} = {name},",
+                key = key.style(self.styles.bibtex_key),
+                name = name.style(self.styles.bibtex_val),
+            ),
+            None => bibtex_kv(f, key, &val, &self.styles),
+        }
+    }
+
+    /// Writes a list of people and, if enabled, their ORCID annotations
+    fn people(&self, f: &mut fmt::Formatter<'_>, key: &str, people: &[Person]) -> fmt::Result {
+        bibtex_people(f, key, people, &self.styles)?;
+        if !self.orcid {
+            return Ok(());
+        }
+        let annotations: Vec<_> = people
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, person)| {
+                let orcid = person.orcid.as_ref()?;
+                Some(format!("{}=\"{orcid}\"", idx + 1))
+            })
+            .collect();
+        if annotations.is_empty() {
+            return Ok(());
+        }
+        bibtex_kv(
+            f,
+            &format!("{key}+an:orcid"),
+            &annotations.join("; "),
+            &self.styles,
+        )
+    }
+
+    /// Writes DOIs and URLs
+    fn externals(&self, f: &mut fmt::Formatter<'_>, external: &[External]) -> fmt::Result {
+        for external in external {
+            match external {
+                External::Url(url) => {
+                    self.kv(f, "url", url)?;
+                }
+                External::Doi(doi) => {
+                    self.kv(f, "doi", doi)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes ISBNs, as one field each in BibTeX and as a single list in biblatex
+    fn isbn(&self, f: &mut fmt::Formatter<'_>, isbn: &[String]) -> fmt::Result {
+        match self.flavor {
+            Flavor::Bibtex => {
+                for isbn in isbn {
+                    self.kv(f, "isbn", isbn)?;
+                }
+                Ok(())
+            }
+            Flavor::Biblatex if isbn.is_empty() => Ok(()),
+            Flavor::Biblatex => self.kv(f, "isbn", &isbn.join(", ")),
+        }
+    }
+
+    /// The arXiv identifier of a CoRR article, if it is written as `@online` entry
+    fn arxiv(&self, key: &str, volume: Option<&str>) -> Option<String> {
+        if self.flavor != Flavor::Biblatex || !key.starts_with("journals/corr/") {
+            return None;
+        }
+        volume?.strip_prefix("abs/").map(String::from)
+    }
+
+    fn start(&self, f: &mut fmt::Formatter<'_>, bibtype: &str, key: &str) -> fmt::Result {
+        let bibtype = self.flavor.entry_type(bibtype);
+        let bibtype = bibtype.style(self.styles.bibtex_type);
+        let Some(alias) = self.alias else {
+            return writeln!(
+                f,
+                "@{bibtype}{{{prefix}{key},",
+                prefix = self.prefix,
+                key = key.style(self.styles.citekey)
+            );
+        };
+        writeln!(
+            f,
+            "@{bibtype}{{{alias},",
+            alias = alias.style(self.styles.citekey)
+        )?;
+        bibtex_kv(f, "ids", &format!("{}{key}", self.prefix), &self.styles)
+    }
 }
 
 fn bibtex_people(
     f: &mut fmt::Formatter<'_>,
     key: &str,
-    people: &[String],
+    people: &[Person],
     styles: &Styles,
 ) -> fmt::Result {
     if people.is_empty() {
@@ -506,256 +937,53 @@ fn bibtex_end(f: &mut fmt::Formatter<'_>) -> fmt::Result {
 
 impl fmt::Display for Bibtex<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.value {
-            Record::Article {
-                key,
-                author,
-                title,
-                pages,
-                year,
-                volume,
-                journal,
-                external,
-            } => {
-                bibtex_start(f, "article", key, &self.styles)?;
-                bibtex_people(f, "author", author, &self.styles)?;
-                bibtex_kv(f, "title", title, &self.styles)?;
-                bibtex_kv(f, "journal", journal, &self.styles)?;
-                bibtex_kv(f, "year", year, &self.styles)?;
-                if let Some(pages) = pages {
-                    bibtex_kv(f, "pages", pages, &self.styles)?;
-                }
-                if let Some(volume) = volume {
-                    bibtex_kv(f, "volume", volume, &self.styles)?;
-                }
-                for external in external {
-                    match external {
-                        External::Url(url) => {
-                            bibtex_kv(f, "url", url, &self.styles)?;
-                        }
-                        External::Doi(doi) => {
-                            bibtex_kv(f, "doi", doi, &self.styles)?;
-                        }
-                    }
-                }
-                bibtex_end(f)
-            }
-            Record::Proceedings {
-                key,
-                editor,
-                title,
-                series,
-                volume,
-                publisher,
-                year,
-                external,
-                isbn,
-            } => {
-                bibtex_start(f, "proceedings", key, &self.styles)?;
-                bibtex_people(f, "editor", editor, &self.styles)?;
-                bibtex_kv(f, "title", title, &self.styles)?;
-                bibtex_kv(f, "year", year, &self.styles)?;
-                if let Some(series) = series {
-                    bibtex_kv(f, "series", series, &self.styles)?;
-                }
-                if let Some(volume) = volume {
-                    bibtex_kv(f, "volume", volume, &self.styles)?;
-                }
-                if let Some(publisher) = publisher {
-                    bibtex_kv(f, "publisher", publisher, &self.styles)?;
-                }
-                for isbn in isbn {
-                    bibtex_kv(f, "isbn", isbn, &self.styles)?;
-                }
-                for external in external {
-                    match external {
-                        External::Url(url) => {
-                            bibtex_kv(f, "url", url, &self.styles)?;
-                        }
-                        External::Doi(doi) => {
-                            bibtex_kv(f, "doi", doi, &self.styles)?;
-                        }
-                    }
-                }
-                bibtex_end(f)
-            }
-            Record::Inproceedings {
-                key,
-                author,
-                title,
-                booktitle,
-                pages,
-                year,
-                external,
-                crossref,
-                usera,
-            } => {
-                bibtex_start(f, "inproceedings", key, &self.styles)?;
-                bibtex_people(f, "author", author, &self.styles)?;
-                bibtex_kv(f, "title", title, &self.styles)?;
-                bibtex_kv(f, "booktitle", booktitle, &self.styles)?;
-                bibtex_kv(f, "year", year, &self.styles)?;
-                if let Some(pages) = pages {
-                    bibtex_kv(f, "pages", pages, &self.styles)?;
-                }
-                for external in external {
-                    match external {
-                        External::Url(url) => {
-                            bibtex_kv(f, "url", url, &self.styles)?;
-                        }
-                        External::Doi(doi) => {
-                            bibtex_kv(f, "doi", doi, &self.styles)?;
-                        }
-                    }
-                }
-                match crossref {
-                    Crossref::Key(key) => {
-                        bibtex_kv(f, "crossref", &format!("DBLP:{key}"), &self.styles)?
-                    }
-                    Crossref::Resolved {
-                        editor,
-                        publisher,
-                        series,
-                        volume,
-                    } => {
-                        bibtex_people(f, "editor", editor, &self.styles)?;
-                        if let Some(series) = series {
-                            bibtex_kv(f, "series", series, &self.styles)?;
-                        }
-                        if let Some(volume) = volume {
-                            bibtex_kv(f, "volume", volume, &self.styles)?;
-                        }
-                        if let Some(publisher) = publisher {
-                            bibtex_kv(f, "publisher", publisher, &self.styles)?;
-                        }
-                    }
-                }
-                if let Some(usera) = usera {
-                    bibtex_kv(f, "usera", usera, &self.styles)?;
-                }
-                bibtex_end(f)
-            }
-            Record::Book {
-                key,
-                author,
-                editor,
-                title,
-                year,
-                volume,
-                series,
-                publisher,
-                external,
-                isbn,
-            } => {
-                bibtex_start(f, "book", key, &self.styles)?;
-                bibtex_people(f, "author", author, &self.styles)?;
-                bibtex_people(f, "editor", editor, &self.styles)?;
-                bibtex_kv(f, "title", title, &self.styles)?;
-                if let Some(publisher) = publisher {
-                    bibtex_kv(f, "publisher", publisher, &self.styles)?;
-                }
-                bibtex_kv(f, "year", year, &self.styles)?;
-                if let Some(series) = series {
-                    bibtex_kv(f, "series", series, &self.styles)?;
-                }
-                if let Some(volume) = volume {
-                    bibtex_kv(f, "volume", volume, &self.styles)?;
-                }
-                for isbn in isbn {
-                    bibtex_kv(f, "isbn", isbn, &self.styles)?;
-                }
-                for external in external {
-                    match external {
-                        External::Url(url) => {
-                            bibtex_kv(f, "url", url, &self.styles)?;
-                        }
-                        External::Doi(doi) => {
-                            bibtex_kv(f, "doi", doi, &self.styles)?;
-                        }
-                    }
-                }
-                bibtex_end(f)
-            }
-            Record::Incollection {
-                key,
-                author,
-                title,
-                booktitle,
-                pages,
-                year,
-                external,
-                crossref,
-            } => {
-                bibtex_start(f, "incollection", key, &self.styles)?;
-                bibtex_people(f, "author", author, &self.styles)?;
-                bibtex_kv(f, "title", title, &self.styles)?;
-                bibtex_kv(f, "booktitle", booktitle, &self.styles)?;
-                bibtex_kv(f, "year", year, &self.styles)?;
-                if let Some(pages) = pages {
-                    bibtex_kv(f, "pages", pages, &self.styles)?;
-                }
-                for external in external {
-                    match external {
-                        External::Url(url) => {
-                            bibtex_kv(f, "url", url, &self.styles)?;
-                        }
-                        External::Doi(doi) => {
-                            bibtex_kv(f, "doi", doi, &self.styles)?;
-                        }
-                    }
-                }
-                match crossref {
-                    Crossref::Key(key) => {
-                        bibtex_kv(f, "crossref", &format!("DBLP:{key}"), &self.styles)?
-                    }
-                    Crossref::Resolved {
-                        editor,
-                        publisher,
-                        series,
-                        volume,
-                    } => {
-                        bibtex_people(f, "editor", editor, &self.styles)?;
-                        if let Some(series) = series {
-                            bibtex_kv(f, "series", series, &self.styles)?;
-                        }
-                        if let Some(volume) = volume {
-                            bibtex_kv(f, "volume", volume, &self.styles)?;
-                        }
-                        if let Some(publisher) = publisher {
-                            bibtex_kv(f, "publisher", publisher, &self.styles)?;
-                        }
-                    }
-                }
-                bibtex_end(f)
+        if let Record::Article {
+            key,
+            author,
+            title,
+            year,
+            volume,
+            external,
+            ..
+        } = self.value
+            && let Some(eprint) = self.arxiv(key, volume.as_deref())
+        {
+            self.start(f, "online", key)?;
+            self.people(f, "author", author)?;
+            self.kv(f, "title", title)?;
+            self.kv(f, "year", year)?;
+            self.kv(f, "eprint", &eprint)?;
+            self.kv(f, "eprinttype", &"arXiv")?;
+            self.externals(f, external)?;
+            return bibtex_end(f);
+        }
+        let bibtype = match self.value {
+            Record::Article { .. } => "article",
+            Record::Proceedings { .. } => "proceedings",
+            Record::Inproceedings { .. } => "inproceedings",
+            // books with editors only are edited volumes
+            Record::Book { author, .. } if author.is_empty() && self.flavor == Flavor::Biblatex => {
+                "collection"
             }
-            Record::Misc {
-                key,
-                author,
-                title,
-                year,
-                publisher,
-                external,
-            } => {
-                bibtex_start(f, "misc", key, &self.styles)?;
-                bibtex_people(f, "author", author, &self.styles)?;
-                bibtex_kv(f, "title", title, &self.styles)?;
-                if let Some(publisher) = publisher {
-                    bibtex_kv(f, "publisher", publisher, &self.styles)?;
-                }
-                bibtex_kv(f, "year", year, &self.styles)?;
-                for external in external {
-                    match external {
-                        External::Url(url) => {
-                            bibtex_kv(f, "url", url, &self.styles)?;
-                        }
-                        External::Doi(doi) => {
-                            bibtex_kv(f, "doi", doi, &self.styles)?;
-                        }
-                    }
+            Record::Book { .. } => "book",
+            Record::Incollection { .. } => "incollection",
+            Record::Misc { .. } => "misc",
+        };
+        self.start(f, bibtype, self.value.key())?;
+        for (name, field) in self.value.field_table() {
+            match field {
+                Field::Text(value) => self.kv(f, name, &value)?,
+                Field::Macro(value) => self.macro_kv(f, name, value)?,
+                Field::Year(year) => self.kv(f, name, &year)?,
+                Field::People(people) => self.people(f, name, people)?,
+                Field::Isbn(isbn) => self.isbn(f, isbn)?,
+                Field::Externals(external) => self.externals(f, external)?,
+                Field::Crossref(key) => {
+                    bibtex_kv(f, name, &format!("{}{key}", self.prefix), &self.styles)?;
                 }
-                bibtex_end(f)
             }
         }
+        bibtex_end(f)
     }
 }
 
@@ -765,11 +993,12 @@ struct XmlRecord {
     value: Data,
 }
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Clone, Debug, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
 enum Data {
     Article {
-        author: Vec<String>,
+        author: Vec<Person>,
         title: String,
         journal: String,
         year: u32,
@@ -779,7 +1008,7 @@ enum Data {
         ee: Vec<String>,
     },
     Inproceedings {
-        author: Vec<String>,
+        author: Vec<Person>,
         title: String,
         pages: Option<String>,
         year: u32,
@@ -789,7 +1018,7 @@ enum Data {
         crossref: String,
     },
     Incollection {
-        author: Vec<String>,
+        author: Vec<Person>,
         title: String,
         year: u32,
         booktitle: String,
@@ -799,7 +1028,7 @@ enum Data {
     },
     Proceedings {
         #[serde(default)]
-        editor: Vec<String>,
+        editor: Vec<Person>,
         title: String,
         year: u32,
         series: Option<String>,
@@ -812,9 +1041,9 @@ enum Data {
     },
     Book {
         #[serde(default)]
-        author: Vec<String>,
+        author: Vec<Person>,
         #[serde(default)]
-        editor: Vec<String>,
+        editor: Vec<Person>,
         title: String,
         publisher: Option<String>,
         year: u32,
@@ -827,7 +1056,7 @@ enum Data {
     },
     Data {
         #[serde(default)]
-        author: Vec<String>,
+        author: Vec<Person>,
         title: String,
         year: u32,
         publisher: Option<String>,
@@ -838,6 +1067,25 @@ enum Data {
 
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn external() {
+        for value in [
+            "https://doi.org/10.1613/jair.1.15333",
+            "http://dx.doi.org/10.1613/jair.1.15333",
+            "doi:10.1613/jair.1.15333",
+            "10.1613/jair.1.15333",
+        ] {
+            let super::External::Doi(doi) = super::External::from(String::from(value)) else {
+                panic!("`{value}` not recognized as DOI");
+            };
+            assert_eq!(doi, "10.1613/jair.1.15333");
+        }
+        assert!(matches!(
+            super::External::from(String::from("https://arxiv.org/abs/2402.01234")),
+            super::External::Url(_)
+        ));
+    }
+
     #[test]
     fn article() {
         let data = r#"
@@ -845,7 +1093,7 @@ mod tests {
             <dblp>
             <article key="journals/jair/JabsBNJ24" mdate="2024-10-06">
             <author>Christoph Jabs</author>
-            <author orcid="0000-0001-7660-8061">Jeremias Berg</author>
+            <author pid="16/10193" orcid="0000-0001-7660-8061">Jeremias Berg</author>
             <author>Andreas Niskanen</author>
             <author>Matti J&#228;rvisalo</author>
             <title>From Single-Objective to Bi-Objective Maximum Satisfiability Solving.</title>
@@ -859,7 +1107,17 @@ mod tests {
             </article>
             </dblp>
         "#;
-        quick_xml::de::from_str::<super::XmlRecord>(data).unwrap();
+        let super::Data::Article { author, .. } = quick_xml::de::from_str::<super::XmlRecord>(data)
+            .unwrap()
+            .value
+        else {
+            panic!("expected an article");
+        };
+        assert_eq!(author[0].name, "Christoph Jabs");
+        assert_eq!(author[0].orcid, None);
+        assert_eq!(author[1].name, "Jeremias Berg");
+        assert_eq!(author[1].pid.as_deref(), Some("16/10193"));
+        assert_eq!(author[1].orcid.as_deref(), Some("0000-0001-7660-8061"));
     }
 
     #[test]
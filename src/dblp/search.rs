@@ -1,15 +1,51 @@
 use std::fmt;
 
 use reqwest::Url;
+use tower::ServiceExt;
 
 mod response;
 
-pub use response::Response;
+pub use response::{Hit, Response};
 
 const PUBL_BASE: &str = "/search/publ/api";
 const AUTHOR_BASE: &str = "/search/author/api";
 const VENUE_BASE: &str = "/search/author/api";
 
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Reqwest HTTP framework error")]
+    Reqwest(#[from] reqwest::Error),
+    #[error("HTTP status code: {0}")]
+    Http(reqwest::StatusCode),
+    #[error("Service error: {0}")]
+    Generic(#[from] Box<dyn std::error::Error + Send + std::marker::Sync + 'static>),
+}
+
+/// The number of search hits checked for a matching DOI
+const DOI_CANDIDATES: u32 = 10;
+
+/// Finds the key of the publication with a DOI, if it is in DBLP
+pub async fn doi_key<Service>(
+    doi: &str,
+    opts: &crate::cli::DblpServerArgs,
+    service: &mut Service,
+) -> Result<Option<String>, Error>
+where
+    Service: tower::Service<
+            reqwest::Request,
+            Response = reqwest::Response,
+            Error = Box<dyn std::error::Error + Send + std::marker::Sync + 'static>,
+        >,
+{
+    let mut query = Query::new(Type::Publication, doi);
+    query.hits(DOI_CANDIDATES);
+    let response = query.get_with_service(opts, service).await?;
+    Ok(response
+        .iter_hits()
+        .find(|hit| hit.doi().is_some_and(|hit| hit.eq_ignore_ascii_case(doi)))
+        .map(|hit| String::from(hit.key())))
+}
+
 #[derive(Clone, Copy, Debug, Default, clap::ValueEnum)]
 pub enum Type {
     #[default]
@@ -43,8 +79,6 @@ pub struct Query {
     qtype: Type,
     query: String,
     hits: Option<u32>,
-    first: Option<u32>,
-    completions: Option<u32>,
 }
 
 impl Query {
@@ -53,8 +87,6 @@ impl Query {
             qtype,
             query: query.into(),
             hits: None,
-            first: None,
-            completions: None,
         }
     }
 
@@ -63,16 +95,6 @@ impl Query {
         self
     }
 
-    pub fn first(&mut self, val: u32) -> &mut Self {
-        self.first = Some(val);
-        self
-    }
-
-    pub fn completions(&mut self, val: u32) -> &mut Self {
-        self.completions = Some(val);
-        self
-    }
-
     pub fn request_url(self, opts: &crate::cli::DblpServerArgs) -> Url {
         let mut url = Url::parse_with_params(
             &format!("{}{}", super::domain(opts), self.qtype.base_url()),
@@ -82,12 +104,6 @@ impl Query {
         if let Some(hits) = self.hits {
             url.query_pairs_mut().append_pair("h", &hits.to_string());
         }
-        if let Some(first) = self.first {
-            url.query_pairs_mut().append_pair("f", &first.to_string());
-        }
-        if let Some(comp) = self.completions {
-            url.query_pairs_mut().append_pair("c", &comp.to_string());
-        }
         url
     }
 
@@ -97,4 +113,31 @@ impl Query {
             .json::<Response>()
             .await
     }
+
+    /// Performs the query through a shared service, e.g., to respect its rate limit
+    pub async fn get_with_service<Service>(
+        self,
+        opts: &crate::cli::DblpServerArgs,
+        service: &mut Service,
+    ) -> Result<Response, Error>
+    where
+        Service: tower::Service<
+                reqwest::Request,
+                Response = reqwest::Response,
+                Error = Box<dyn std::error::Error + Send + std::marker::Sync + 'static>,
+            >,
+    {
+        let response = service
+            .ready()
+            .await?
+            .call(reqwest::Request::new(
+                reqwest::Method::GET,
+                self.request_url(opts),
+            ))
+            .await?;
+        if !response.status().is_success() {
+            return Err(Error::Http(response.status()));
+        }
+        Ok(response.json::<Response>().await?)
+    }
 }
@@ -10,39 +10,20 @@ pub struct Response {
 }
 
 impl Response {
-    pub fn iter_hits(&self) -> std::slice::Iter<Hit> {
+    pub fn iter_hits(&self) -> std::slice::Iter<'_, Hit> {
         self.result.hits.hits.iter()
     }
 }
 
 #[derive(serde::Deserialize, Clone, Debug)]
 struct Result {
-    completions: Completions,
     hits: Hits,
 }
 
-#[derive(serde::Deserialize, Clone, Debug)]
-struct Completions {
-    #[serde(rename = "@total")]
-    #[serde(deserialize_with = "crate::serde_utils::deserialize_number_from_string")]
-    total: u32,
-    #[serde(rename = "c", with = "crate::serde_utils::maybe_single")]
-    completions: Vec<Completion>,
-}
-
-#[derive(serde::Deserialize, Clone, Debug)]
-struct Completion {
-    text: String,
-}
-
 #[derive(serde::Deserialize, Clone, Debug)]
 pub struct Hits {
-    #[serde(
-        rename = "@total",
-        deserialize_with = "crate::serde_utils::deserialize_number_from_string"
-    )]
-    total: u32,
-    #[serde(rename = "hit", with = "crate::serde_utils::maybe_single")]
+    // missing if there are no hits
+    #[serde(rename = "hit", with = "crate::serde_utils::maybe_single", default)]
     hits: Vec<Hit>,
 }
 
@@ -58,6 +39,57 @@ impl Hit {
             styles: Box::default(),
         }
     }
+
+    fn common(&self) -> &CommonInfo {
+        match &self.info {
+            Info::BooksAndTheses { common }
+            | Info::ConferenceAndWorkshopPapers { common, .. }
+            | Info::JournalArticles { common, .. }
+            | Info::PartsInBooksOrCollections { common, .. }
+            | Info::InformalAndOtherPublications { common, .. }
+            | Info::DataAndArtifacts { common, .. } => common,
+        }
+    }
+
+    /// The DBLP key without prefix
+    pub fn key(&self) -> &str {
+        &self.common().key
+    }
+
+    pub fn title(&self) -> &str {
+        &self.common().title
+    }
+
+    /// The author names as displayed by DBLP, e.g., `João Marques-Silva 0001`
+    pub fn authors(&self) -> impl Iterator<Item = &str> {
+        self.common()
+            .authors
+            .author
+            .iter()
+            .map(|author| author.text.as_str())
+    }
+
+    pub fn year(&self) -> u32 {
+        self.common().year
+    }
+
+    pub fn doi(&self) -> Option<&str> {
+        match &self.info {
+            Info::ConferenceAndWorkshopPapers {
+                paper: CommonPaperInfo { doi, .. },
+                ..
+            }
+            | Info::JournalArticles {
+                paper: CommonPaperInfo { doi, .. },
+                ..
+            }
+            | Info::PartsInBooksOrCollections { doi, .. }
+            | Info::InformalAndOtherPublications { doi, .. } => {
+                Some(doi.as_str()).filter(|doi| !doi.is_empty())
+            }
+            Info::BooksAndTheses { .. } | Info::DataAndArtifacts { .. } => None,
+        }
+    }
 }
 
 #[derive(serde::Deserialize, Clone, Debug)]
@@ -81,6 +113,7 @@ enum Access {
 #[derive(serde::Deserialize, Clone, Debug)]
 struct CommonPaperInfo {
     venue: String,
+    #[serde(default)]
     doi: String,
 }
 
@@ -111,6 +144,7 @@ enum Info {
         #[serde(flatten)]
         common: CommonInfo,
         venue: String,
+        #[serde(default)]
         doi: String,
     },
     #[serde(rename = "Informal and Other Publications")]
@@ -118,6 +152,7 @@ enum Info {
         #[serde(flatten)]
         common: CommonInfo,
         venue: String,
+        #[serde(default)]
         doi: String,
     },
     #[serde(rename = "Data and Artifacts")]
@@ -153,14 +188,7 @@ impl HitDisplay<'_> {
 
 impl fmt::Display for HitDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let common = match &self.value.info {
-            Info::BooksAndTheses { common }
-            | Info::ConferenceAndWorkshopPapers { common, .. }
-            | Info::JournalArticles { common, .. }
-            | Info::PartsInBooksOrCollections { common, .. }
-            | Info::InformalAndOtherPublications { common, .. }
-            | Info::DataAndArtifacts { common, .. } => common,
-        };
+        let common = self.value.common();
         writeln!(f, "DBLP:{}", common.key.style(self.styles.citekey))?;
         writeln!(f, "{}", "----------".style(self.styles.separator))?;
         for (idx, author) in common.authors.author.iter().enumerate() {
@@ -188,25 +216,13 @@ impl fmt::Display for HitDisplay<'_> {
             _ => {}
         };
         writeln!(f, "({})", common.year.style(self.styles.year))?;
-        match &self.value.info {
-            Info::ConferenceAndWorkshopPapers {
-                paper: CommonPaperInfo { doi, .. },
-                ..
-            }
-            | Info::JournalArticles {
-                paper: CommonPaperInfo { doi, .. },
-                ..
-            }
-            | Info::PartsInBooksOrCollections { doi, .. }
-            | Info::InformalAndOtherPublications { doi, .. } => {
-                write!(
-                    f,
-                    "{} ",
-                    format!("https://doi.org/{doi}").style(self.styles.url)
-                )?;
-            }
-            _ => {}
-        };
+        if let Some(doi) = self.value.doi() {
+            write!(
+                f,
+                "{} ",
+                format!("https://doi.org/{doi}").style(self.styles.url)
+            )?;
+        }
         match common.access {
             Access::Open => writeln!(f, "[{}]", "open access".style(self.styles.open_access)),
             Access::Closed => writeln!(f, "[{}]", "closed access".style(self.styles.closed_access)),
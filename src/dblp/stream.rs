@@ -7,14 +7,16 @@ use std::fmt;
 use tower::ServiceExt;
 
 use super::record::Error;
+use crate::venues::Journal;
 
 const BASE: &str = "/streams/";
 
-pub async fn journal_title<Service>(
+/// Fetches the metadata of a journal, e.g., `jair`
+pub async fn journal<Service>(
     key: &str,
     opts: &crate::cli::DblpServerArgs,
     service: &mut Service,
-) -> Result<String, Error>
+) -> Result<Journal, Error>
 where
     Service: tower::Service<
             reqwest::Request,
@@ -36,9 +38,16 @@ where
         code if !code.is_success() => return Err(Error::Http(code)),
         _ => {}
     }
-    let Data::Journal { title, .. } =
-        quick_xml::de::from_str::<XmlRecord>(&response.text().await?)?.value;
-    Ok(format!("{title}"))
+    let Data::Journal {
+        title,
+        acronym,
+        iso4,
+    } = quick_xml::de::from_str::<XmlRecord>(&response.text().await?)?.value;
+    Ok(Journal {
+        title: title.to_string(),
+        acronym,
+        iso4,
+    })
 }
 
 #[derive(Clone, Debug, serde::Deserialize)]
@@ -50,7 +59,14 @@ struct XmlRecord {
 #[derive(Clone, Debug, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
 enum Data {
-    Journal { title: Title },
+    Journal {
+        title: Title,
+        /// Not provided for all journals
+        #[serde(default)]
+        acronym: Option<String>,
+        #[serde(default)]
+        iso4: Option<String>,
+    },
 }
 
 #[derive(Clone, Debug, serde::Deserialize)]
@@ -0,0 +1,447 @@
+//! # Export Formats
+//!
+//! Besides BibTeX and biblatex, records can be written as CSL-JSON (Zotero, Pandoc, Word
+//! plugins), RIS and Hayagriva YAML (Typst). These formats are not read by TeX, so the fixers
+//! producing LaTeX, and title casing, are skipped for them and any remaining LaTeX is converted to
+//! plain text.
+
+use crate::{
+    dblp::{
+        Record,
+        record::{Crossref, External, Flavor, Person},
+    },
+    fixers::{names::Name, unicode::plain_text},
+};
+
+mod csl;
+mod hayagriva;
+mod ris;
+
+/// Fixers that write LaTeX markup, which are skipped for formats not read by TeX
+///
+/// Casing is skipped as well, since it relies on the braces of the other fixers to keep acronyms
+/// and proper nouns, and the consumers of these formats case titles themselves.
+pub const TEX_ONLY_FIXERS: [&str; 7] = [
+    "math",
+    "escape_latex",
+    "capital_after_colon",
+    "proper_nouns",
+    "acronyms",
+    "casing",
+    "unicode",
+];
+
+#[derive(
+    clap::ValueEnum,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    /// Classic BibTeX
+    #[default]
+    Bibtex,
+    /// Native biblatex
+    Biblatex,
+    /// CSL-JSON, as read by Zotero and Pandoc
+    CslJson,
+    /// RIS, as read by Zotero, EndNote and Word
+    Ris,
+    /// Hayagriva YAML, as read by Typst
+    Hayagriva,
+}
+
+impl Format {
+    /// The flavor of BibTeX for formats read by TeX
+    pub fn flavor(self) -> Option<Flavor> {
+        match self {
+            Format::Bibtex => Some(Flavor::Bibtex),
+            Format::Biblatex => Some(Flavor::Biblatex),
+            Format::CslJson | Format::Ris | Format::Hayagriva => None,
+        }
+    }
+
+    /// The export format for formats not read by TeX
+    pub fn export(self) -> Option<Export> {
+        match self {
+            Format::Bibtex | Format::Biblatex => None,
+            Format::CslJson => Some(Export::CslJson),
+            Format::Ris => Some(Export::Ris),
+            Format::Hayagriva => Some(Export::Hayagriva),
+        }
+    }
+
+    /// Whether the format is read by TeX and fields should contain LaTeX
+    pub fn is_tex(self) -> bool {
+        self.flavor().is_some()
+    }
+}
+
+/// An output format not read by TeX, written by [`render`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Export {
+    CslJson,
+    Ris,
+    Hayagriva,
+}
+
+/// The kind of an exported item
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Article,
+    /// An arXiv preprint, listed as CoRR article by DBLP
+    Preprint,
+    /// A paper in conference proceedings
+    Paper,
+    /// A chapter in an edited book
+    Chapter,
+    Proceedings,
+    Book,
+    /// A book with editors only
+    EditedBook,
+    Data,
+}
+
+/// A record with its fields converted to plain text, the common ground of all export formats
+#[derive(Clone, Debug)]
+struct Item {
+    key: String,
+    kind: Kind,
+    author: Vec<Name>,
+    editor: Vec<Name>,
+    title: String,
+    /// The journal or proceedings title
+    container: Option<String>,
+    year: u32,
+    pages: Option<String>,
+    /// The volume of a journal, or of a book in its series
+    volume: Option<String>,
+    series: Option<String>,
+    publisher: Option<String>,
+    isbn: Vec<String>,
+    /// The arXiv identifier of a preprint
+    eprint: Option<String>,
+    doi: Option<String>,
+    url: Option<String>,
+}
+
+fn names(people: &[Person]) -> Vec<Name> {
+    people
+        .iter()
+        .filter_map(|person| Name::parse_bibtex(&plain_text(&person.name)))
+        .collect()
+}
+
+/// A name as `<particle> <family>, <given>, <suffix>`, as read by RIS and Hayagriva
+fn inverted(name: &Name) -> String {
+    let mut out = name.family.clone();
+    if !name.particle.is_empty() {
+        out = format!("{} {out}", name.particle);
+    }
+    if !name.given.is_empty() || !name.suffix.is_empty() {
+        out.push_str(", ");
+        out.push_str(&name.given);
+    }
+    if !name.suffix.is_empty() {
+        out.push_str(", ");
+        out.push_str(&name.suffix);
+    }
+    out
+}
+
+/// Pages with a single hyphen, e.g., `1223-1269`
+fn pages(pages: &Option<String>) -> Option<String> {
+    pages
+        .as_ref()
+        .map(|pages| plain_text(&pages.replace("--", "-")))
+}
+
+impl Item {
+    /// Converts a record, taking the proceedings data of crossref-style records from `records`
+    fn new(key: String, rec: &Record, records: &[&Record]) -> Self {
+        let text = |value: &Option<String>| value.as_deref().map(plain_text);
+        let mut item = Self {
+            key,
+            kind: Kind::Data,
+            author: vec![],
+            editor: vec![],
+            title: String::new(),
+            container: None,
+            year: 0,
+            pages: None,
+            volume: None,
+            series: None,
+            publisher: None,
+            isbn: vec![],
+            eprint: None,
+            doi: None,
+            url: None,
+        };
+        let external = match rec {
+            Record::Article {
+                key,
+                author,
+                title,
+                journal,
+                year,
+                pages: article_pages,
+                volume,
+                external,
+            } => {
+                let eprint = volume
+                    .as_deref()
+                    .and_then(|volume| volume.strip_prefix("abs/"))
+                    .filter(|_| key.starts_with("journals/corr/"));
+                match eprint {
+                    Some(eprint) => {
+                        item.kind = Kind::Preprint;
+                        item.container = Some(String::from("arXiv"));
+                        item.eprint = Some(String::from(eprint));
+                    }
+                    None => {
+                        item.kind = Kind::Article;
+                        item.container = Some(plain_text(journal));
+                        item.volume = text(volume);
+                    }
+                }
+                item.author = names(author);
+                item.title = plain_text(title);
+                item.year = *year;
+                item.pages = pages(article_pages);
+                external
+            }
+            Record::Inproceedings {
+                author,
+                title,
+                booktitle,
+                year,
+                pages: paper_pages,
+                external,
+                crossref,
+                ..
+            }
+            | Record::Incollection {
+                author,
+                title,
+                booktitle,
+                year,
+                pages: paper_pages,
+                external,
+                crossref,
+                ..
+            } => {
+                item.kind = if matches!(rec, Record::Inproceedings { .. }) {
+                    Kind::Paper
+                } else {
+                    Kind::Chapter
+                };
+                item.author = names(author);
+                item.title = plain_text(title);
+                item.container = Some(plain_text(booktitle));
+                item.year = *year;
+                item.pages = pages(paper_pages);
+                match crossref {
+                    Crossref::Resolved {
+                        editor,
+                        publisher,
+                        series,
+                        volume,
+                    } => {
+                        item.editor = names(editor);
+                        item.publisher = text(publisher);
+                        item.series = text(series);
+                        item.volume = text(volume);
+                    }
+                    Crossref::Key(key) => {
+                        if let Some(
+                            Record::Proceedings {
+                                editor,
+                                series,
+                                volume,
+                                publisher,
+                                isbn,
+                                ..
+                            }
+                            | Record::Book {
+                                editor,
+                                series,
+                                volume,
+                                publisher,
+                                isbn,
+                                ..
+                            },
+                        ) = records.iter().find(|rec| rec.key() == key)
+                        {
+                            item.editor = names(editor);
+                            item.publisher = text(publisher);
+                            item.series = text(series);
+                            item.volume = text(volume);
+                            item.isbn.clone_from(isbn);
+                        }
+                    }
+                }
+                external
+            }
+            Record::Proceedings {
+                editor,
+                title,
+                year,
+                series,
+                volume,
+                publisher,
+                external,
+                isbn,
+                ..
+            } => {
+                item.kind = Kind::Proceedings;
+                item.editor = names(editor);
+                item.title = plain_text(title);
+                item.year = *year;
+                item.series = text(series);
+                item.volume = text(volume);
+                item.publisher = text(publisher);
+                item.isbn.clone_from(isbn);
+                external
+            }
+            Record::Book {
+                author,
+                editor,
+                title,
+                year,
+                series,
+                volume,
+                publisher,
+                external,
+                isbn,
+                ..
+            } => {
+                item.kind = if author.is_empty() {
+                    Kind::EditedBook
+                } else {
+                    Kind::Book
+                };
+                item.author = names(author);
+                item.editor = names(editor);
+                item.title = plain_text(title);
+                item.year = *year;
+                item.series = text(series);
+                item.volume = text(volume);
+                item.publisher = text(publisher);
+                item.isbn.clone_from(isbn);
+                external
+            }
+            Record::Misc {
+                author,
+                title,
+                year,
+                publisher,
+                external,
+                ..
+            } => {
+                item.kind = Kind::Data;
+                item.author = names(author);
+                item.title = plain_text(title);
+                item.year = *year;
+                item.publisher = text(publisher);
+                external
+            }
+        };
+        for external in external {
+            match external {
+                External::Doi(doi) => {
+                    item.doi.get_or_insert_with(|| doi.clone());
+                }
+                External::Url(url) => {
+                    item.url.get_or_insert_with(|| url.clone());
+                }
+            }
+        }
+        item
+    }
+}
+
+/// Writes records in a format not read by TeX
+///
+/// The records are given with the citation key to write them under. Crossref-style records take
+/// their proceedings data from the crossref record, if it is among the written records. Formats
+/// read by TeX are written with [`Record::bibtex`].
+pub fn render<'a>(
+    format: Export,
+    records: impl IntoIterator<Item = (String, &'a Record)>,
+) -> String {
+    let records: Vec<_> = records.into_iter().collect();
+    let all: Vec<_> = records.iter().map(|(_, rec)| *rec).collect();
+    let items: Vec<_> = records
+        .iter()
+        .map(|(key, rec)| Item::new(key.clone(), rec, &all))
+        .collect();
+    match format {
+        Export::CslJson => csl::render(&items),
+        Export::Ris => ris::render(&items),
+        Export::Hayagriva => hayagriva::render(&items),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dblp::{
+        Record,
+        record::{Crossref, External, Person},
+    };
+
+    /// A crossref-style paper and its proceedings, after the fixers for non-TeX formats
+    pub(super) fn records() -> [Record; 2] {
+        [
+            Record::Inproceedings {
+                key: String::from("conf/cp/JabsBIJ23"),
+                author: vec![
+                    Person::from("Jabs, Christoph"),
+                    Person::from("J{\\\"a}rvisalo, Matti"),
+                ],
+                title: String::from("Preprocessing in SAT-Based Multi-Objective Optimization"),
+                booktitle: String::from("CP 2023"),
+                year: 2023,
+                pages: Some(String::from("18:1--18:20")),
+                external: vec![External::Doi(String::from("10.4230/LIPIcs.CP.2023.18"))],
+                crossref: Crossref::Key(String::from("conf/cp/2023")),
+                usera: None,
+            },
+            Record::Proceedings {
+                key: String::from("conf/cp/2023"),
+                editor: vec![Person::from("Yap, Roland H. C.")],
+                title: String::from("Principles and Practice of Constraint Programming"),
+                year: 2023,
+                series: Some(String::from("LIPIcs")),
+                volume: Some(String::from("280")),
+                publisher: Some(String::from(
+                    "Schloss Dagstuhl - Leibniz-Zentrum für Informatik",
+                )),
+                external: vec![External::Url(String::from(
+                    "https://www.dagstuhl.de/dagpub/978-3-95977-300-3",
+                ))],
+                isbn: vec![String::from("978-3-95977-300-3")],
+            },
+        ]
+    }
+
+    #[test]
+    fn items() {
+        let records = records();
+        let all: Vec<_> = records.iter().collect();
+        let item = super::Item::new(String::from("DBLP:conf/cp/JabsBIJ23"), &records[0], &all);
+        assert_eq!(item.kind, super::Kind::Paper);
+        assert_eq!(item.author[1].family, "Järvisalo");
+        assert_eq!(item.pages.as_deref(), Some("18:1-18:20"));
+        // taken from the crossref record
+        assert_eq!(item.editor[0].given, "Roland H. C.");
+        assert_eq!(item.series.as_deref(), Some("LIPIcs"));
+        assert_eq!(item.isbn, ["978-3-95977-300-3"]);
+        assert_eq!(item.doi.as_deref(), Some("10.4230/LIPIcs.CP.2023.18"));
+    }
+}
@@ -0,0 +1,158 @@
+//! # CSL-JSON
+//!
+//! The [CSL-JSON](https://citeproc-js.readthedocs.io/en/latest/csl-json/markup.html) format, an
+//! array of items with CSL variables.
+
+use serde_json::{Map, Value, json};
+
+use super::{Item, Kind};
+use crate::fixers::names::Name;
+
+fn csl_type(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Article => "article-journal",
+        Kind::Preprint => "article",
+        Kind::Paper => "paper-conference",
+        Kind::Chapter => "chapter",
+        Kind::Proceedings | Kind::Book | Kind::EditedBook => "book",
+        Kind::Data => "dataset",
+    }
+}
+
+fn names(names: &[Name]) -> Value {
+    names
+        .iter()
+        .map(|name| {
+            let mut csl = Map::new();
+            for (key, value) in [
+                ("family", &name.family),
+                ("given", &name.given),
+                ("non-dropping-particle", &name.particle),
+                ("suffix", &name.suffix),
+            ] {
+                if !value.is_empty() {
+                    csl.insert(String::from(key), json!(value));
+                }
+            }
+            Value::Object(csl)
+        })
+        .collect()
+}
+
+fn item(item: &Item) -> Value {
+    let mut csl = Map::new();
+    let mut set = |key: &str, value: Value| {
+        csl.insert(String::from(key), value);
+    };
+    set("id", json!(item.key));
+    set("type", json!(csl_type(item.kind)));
+    if !item.author.is_empty() {
+        set("author", names(&item.author));
+    }
+    if !item.editor.is_empty() {
+        set("editor", names(&item.editor));
+    }
+    set("title", json!(item.title));
+    set("issued", json!({ "date-parts": [[item.year]] }));
+    if let Some(container) = &item.container {
+        set("container-title", json!(container));
+    }
+    if let Some(pages) = &item.pages {
+        set("page", json!(pages));
+    }
+    // the volume of a book in a series is the number in the collection
+    if let Some(volume) = &item.volume {
+        if item.series.is_some() {
+            set("collection-number", json!(volume));
+        } else {
+            set("volume", json!(volume));
+        }
+    }
+    if let Some(series) = &item.series {
+        set("collection-title", json!(series));
+    }
+    if let Some(publisher) = &item.publisher {
+        set("publisher", json!(publisher));
+    }
+    if let Some(eprint) = &item.eprint {
+        set("number", json!(format!("arXiv:{eprint}")));
+    }
+    if !item.isbn.is_empty() {
+        set("ISBN", json!(item.isbn.join(" ")));
+    }
+    if let Some(doi) = &item.doi {
+        set("DOI", json!(doi));
+    }
+    if let Some(url) = &item.url {
+        set("URL", json!(url));
+    }
+    Value::Object(csl)
+}
+
+pub fn render(items: &[Item]) -> String {
+    let items: Vec<_> = items.iter().map(item).collect();
+    serde_json::to_string_pretty(&items).expect("JSON values are always serializable")
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn render() {
+        let records = super::super::tests::records();
+        let out = super::super::render(
+            super::super::Export::CslJson,
+            records
+                .iter()
+                .map(|rec| (format!("DBLP:{}", rec.key()), rec)),
+        );
+        let items: serde_json::Value = serde_json::from_str(&out).unwrap();
+        let paper = &items[0];
+        assert_eq!(paper["id"], "DBLP:conf/cp/JabsBIJ23");
+        assert_eq!(paper["type"], "paper-conference");
+        assert_eq!(
+            paper["author"][1],
+            serde_json::json!({ "family": "Järvisalo", "given": "Matti" })
+        );
+        assert_eq!(paper["issued"]["date-parts"][0][0], 2023);
+        assert_eq!(paper["container-title"], "CP 2023");
+        assert_eq!(paper["collection-title"], "LIPIcs");
+        assert_eq!(paper["collection-number"], "280");
+        assert_eq!(paper["DOI"], "10.4230/LIPIcs.CP.2023.18");
+        assert_eq!(items[1]["type"], "book");
+        assert_eq!(items[1]["ISBN"], "978-3-95977-300-3");
+    }
+
+    #[test]
+    fn casing_skipped() {
+        use clap::Parser;
+
+        let args = crate::cli::Args::parse_from([
+            "dblp",
+            "get",
+            "--casing=sentence",
+            "--output-format=csl-json",
+            "conf/cp/JabsBIJ23",
+        ]);
+        let crate::cli::Commands::Get(mut get) = args.command else {
+            panic!("parsed wrong command");
+        };
+        get.common.build_pipeline().unwrap();
+        let mut records = super::super::tests::records();
+        for rec in &mut records {
+            get.common
+                .pipeline
+                .apply(rec, &mut crate::fixers::pipeline::FixReport::default());
+        }
+        let out = super::super::render(
+            super::super::Export::CslJson,
+            records
+                .iter()
+                .map(|rec| (format!("DBLP:{}", rec.key()), rec)),
+        );
+        let items: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(
+            items[0]["title"],
+            "Preprocessing in SAT-Based Multi-Objective Optimization"
+        );
+    }
+}
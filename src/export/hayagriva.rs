@@ -0,0 +1,225 @@
+//! # Hayagriva YAML
+//!
+//! The [Hayagriva](https://github.com/typst/hayagriva/blob/main/docs/file-format.md) format read
+//! by Typst, a YAML map from citation keys to entries. Journals, proceedings and series are
+//! written as nested `parent` entries.
+
+use std::fmt::{self, Write};
+
+use super::{Item, Kind};
+use crate::fixers::names::Name;
+
+/// A YAML value
+enum Value {
+    Str(String),
+    Int(u32),
+    List(Vec<String>),
+    Map(Vec<(&'static str, Value)>),
+}
+
+fn string(value: &str) -> Value {
+    Value::Str(String::from(value))
+}
+
+fn people(names: &[Name]) -> Value {
+    Value::List(names.iter().map(super::inverted).collect())
+}
+
+/// Double-quoted YAML scalar, JSON strings are valid YAML
+fn quoted(value: &str) -> String {
+    serde_json::to_string(value).expect("strings are always serializable")
+}
+
+fn write(out: &mut String, indent: usize, fields: &[(&str, Value)]) -> fmt::Result {
+    let pad = " ".repeat(indent);
+    for (key, value) in fields {
+        match value {
+            Value::Str(value) => writeln!(out, "{pad}{key}: {}", quoted(value))?,
+            Value::Int(value) => writeln!(out, "{pad}{key}: {value}")?,
+            Value::List(values) => {
+                writeln!(out, "{pad}{key}:")?;
+                for value in values {
+                    writeln!(out, "{pad}  - {}", quoted(value))?;
+                }
+            }
+            Value::Map(fields) => {
+                writeln!(out, "{pad}{key}:")?;
+                write(out, indent + 2, fields)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The series of a book as parent entry
+fn series(item: &Item, fields: &mut Vec<(&'static str, Value)>) {
+    if let Some(series) = &item.series {
+        fields.push((
+            "parent",
+            Value::Map(vec![("type", string("book")), ("title", string(series))]),
+        ));
+    }
+}
+
+/// The fields describing a book, i.e., the proceedings or edited book a paper appeared in
+fn book(item: &Item, fields: &mut Vec<(&'static str, Value)>) {
+    if !item.editor.is_empty() {
+        fields.push(("editor", people(&item.editor)));
+    }
+    if let Some(publisher) = &item.publisher {
+        fields.push(("publisher", string(publisher)));
+    }
+    if let Some(volume) = &item.volume {
+        fields.push(("volume", string(volume)));
+    }
+}
+
+fn serial_numbers(item: &Item, isbn: bool) -> Option<Value> {
+    let mut numbers = vec![];
+    if let Some(doi) = &item.doi {
+        numbers.push(("doi", string(doi)));
+    }
+    if let Some(eprint) = &item.eprint {
+        numbers.push(("arxiv", string(eprint)));
+    }
+    if isbn && let Some(first) = item.isbn.first() {
+        numbers.push(("isbn", string(first)));
+    }
+    (!numbers.is_empty()).then_some(Value::Map(numbers))
+}
+
+fn entry(item: &Item) -> Vec<(&'static str, Value)> {
+    let entry_type = match item.kind {
+        Kind::Article | Kind::Preprint | Kind::Paper => "article",
+        Kind::Chapter => "chapter",
+        Kind::Proceedings => "proceedings",
+        Kind::Book => "book",
+        Kind::EditedBook => "anthology",
+        Kind::Data => "repository",
+    };
+    let mut fields = vec![("type", string(entry_type)), ("title", string(&item.title))];
+    if !item.author.is_empty() {
+        fields.push(("author", people(&item.author)));
+    }
+    fields.push(("date", Value::Int(item.year)));
+    if let Some(pages) = &item.pages {
+        fields.push(("page-range", string(pages)));
+    }
+    let own_book = matches!(
+        item.kind,
+        Kind::Proceedings | Kind::Book | Kind::EditedBook | Kind::Data
+    );
+    if own_book {
+        book(item, &mut fields);
+    }
+    if let Some(numbers) = serial_numbers(item, own_book) {
+        fields.push(("serial-number", numbers));
+    }
+    if let Some(url) = &item.url {
+        fields.push(("url", string(url)));
+    }
+    match item.kind {
+        Kind::Article | Kind::Preprint => {
+            let parent_type = if item.kind == Kind::Preprint {
+                "repository"
+            } else {
+                "periodical"
+            };
+            let mut parent = vec![("type", string(parent_type))];
+            if let Some(container) = &item.container {
+                parent.push(("title", string(container)));
+            }
+            if let Some(volume) = &item.volume {
+                parent.push(("volume", string(volume)));
+            }
+            fields.push(("parent", Value::Map(parent)));
+        }
+        Kind::Paper | Kind::Chapter => {
+            let parent_type = if item.kind == Kind::Paper {
+                "proceedings"
+            } else {
+                "anthology"
+            };
+            let mut parent = vec![("type", string(parent_type))];
+            if let Some(container) = &item.container {
+                parent.push(("title", string(container)));
+            }
+            book(item, &mut parent);
+            if let Some(isbn) = item.isbn.first() {
+                parent.push(("serial-number", Value::Map(vec![("isbn", string(isbn))])));
+            }
+            series(item, &mut parent);
+            fields.push(("parent", Value::Map(parent)));
+        }
+        Kind::Proceedings | Kind::Book | Kind::EditedBook | Kind::Data => series(item, &mut fields),
+    }
+    fields
+}
+
+pub fn render(items: &[Item]) -> String {
+    let mut out = String::new();
+    for (idx, item) in items.iter().enumerate() {
+        if idx > 0 {
+            out.push('\n');
+        }
+        writeln!(out, "{}:", quoted(&item.key))
+            .and_then(|()| write(&mut out, 2, &entry(item)))
+            .expect("writing to a string cannot fail");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn render() {
+        let records = super::super::tests::records();
+        let out = super::super::render(
+            super::super::Export::Hayagriva,
+            records
+                .iter()
+                .map(|rec| (format!("DBLP:{}", rec.key()), rec)),
+        );
+        assert_eq!(
+            out,
+            r#""DBLP:conf/cp/JabsBIJ23":
+  type: "article"
+  title: "Preprocessing in SAT-Based Multi-Objective Optimization"
+  author:
+    - "Jabs, Christoph"
+    - "Järvisalo, Matti"
+  date: 2023
+  page-range: "18:1-18:20"
+  serial-number:
+    doi: "10.4230/LIPIcs.CP.2023.18"
+  parent:
+    type: "proceedings"
+    title: "CP 2023"
+    editor:
+      - "Yap, Roland H. C."
+    publisher: "Schloss Dagstuhl - Leibniz-Zentrum für Informatik"
+    volume: "280"
+    serial-number:
+      isbn: "978-3-95977-300-3"
+    parent:
+      type: "book"
+      title: "LIPIcs"
+
+"DBLP:conf/cp/2023":
+  type: "proceedings"
+  title: "Principles and Practice of Constraint Programming"
+  date: 2023
+  editor:
+    - "Yap, Roland H. C."
+  publisher: "Schloss Dagstuhl - Leibniz-Zentrum für Informatik"
+  volume: "280"
+  serial-number:
+    isbn: "978-3-95977-300-3"
+  url: "https://www.dagstuhl.de/dagpub/978-3-95977-300-3"
+  parent:
+    type: "book"
+    title: "LIPIcs"
+"#
+        );
+    }
+}
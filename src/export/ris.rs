@@ -0,0 +1,127 @@
+//! # RIS
+//!
+//! The tagged [RIS](https://en.wikipedia.org/wiki/RIS_(file_format)) format, one `TAG  - value`
+//! line per field and records ending with `ER  - `.
+
+use std::fmt::Write;
+
+use super::{Item, Kind};
+
+fn ris_type(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Article => "JOUR",
+        Kind::Preprint => "UNPB",
+        Kind::Paper => "CPAPER",
+        Kind::Chapter => "CHAP",
+        Kind::Proceedings => "CONF",
+        Kind::Book => "BOOK",
+        Kind::EditedBook => "EDBOOK",
+        Kind::Data => "DATA",
+    }
+}
+
+fn item(out: &mut String, item: &Item) {
+    let mut tag = |tag: &str, value: &str| {
+        writeln!(out, "{tag}  - {value}").expect("writing to a string cannot fail");
+    };
+    tag("TY", ris_type(item.kind));
+    tag("ID", &item.key);
+    for author in &item.author {
+        tag("AU", &super::inverted(author));
+    }
+    for editor in &item.editor {
+        tag("ED", &super::inverted(editor));
+    }
+    tag("TI", &item.title);
+    if let Some(container) = &item.container {
+        tag("T2", container);
+    }
+    if let Some(series) = &item.series {
+        tag("T3", series);
+    }
+    tag("PY", &item.year.to_string());
+    if let Some(pages) = &item.pages {
+        match pages.split_once('-') {
+            Some((start, end)) => {
+                tag("SP", start);
+                tag("EP", end);
+            }
+            None => tag("SP", pages),
+        }
+    }
+    if let Some(volume) = &item.volume {
+        tag("VL", volume);
+    }
+    if let Some(publisher) = &item.publisher {
+        tag("PB", publisher);
+    }
+    if let Some(eprint) = &item.eprint {
+        tag("M1", &format!("arXiv:{eprint}"));
+    }
+    for isbn in &item.isbn {
+        tag("SN", isbn);
+    }
+    if let Some(doi) = &item.doi {
+        tag("DO", doi);
+    }
+    if let Some(url) = &item.url {
+        tag("UR", url);
+    }
+    tag("ER", "");
+}
+
+pub fn render(items: &[Item]) -> String {
+    let mut out = String::new();
+    for (idx, rec) in items.iter().enumerate() {
+        if idx > 0 {
+            out.push('\n');
+        }
+        item(&mut out, rec);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn render() {
+        let records = super::super::tests::records();
+        let out = super::super::render(
+            super::super::Export::Ris,
+            records
+                .iter()
+                .map(|rec| (format!("DBLP:{}", rec.key()), rec)),
+        );
+        assert_eq!(
+            out,
+            "TY  - CPAPER\n\
+            ID  - DBLP:conf/cp/JabsBIJ23\n\
+            AU  - Jabs, Christoph\n\
+            AU  - Järvisalo, Matti\n\
+            ED  - Yap, Roland H. C.\n\
+            TI  - Preprocessing in SAT-Based Multi-Objective Optimization\n\
+            T2  - CP 2023\n\
+            T3  - LIPIcs\n\
+            PY  - 2023\n\
+            SP  - 18:1\n\
+            EP  - 18:20\n\
+            VL  - 280\n\
+            PB  - Schloss Dagstuhl - Leibniz-Zentrum für Informatik\n\
+            SN  - 978-3-95977-300-3\n\
+            DO  - 10.4230/LIPIcs.CP.2023.18\n\
+            ER  - \n\
+            \n\
+            TY  - CONF\n\
+            ID  - DBLP:conf/cp/2023\n\
+            ED  - Yap, Roland H. C.\n\
+            TI  - Principles and Practice of Constraint Programming\n\
+            T3  - LIPIcs\n\
+            PY  - 2023\n\
+            VL  - 280\n\
+            PB  - Schloss Dagstuhl - Leibniz-Zentrum für Informatik\n\
+            SN  - 978-3-95977-300-3\n\
+            UR  - https://www.dagstuhl.de/dagpub/978-3-95977-300-3\n\
+            ER  - \n"
+        );
+    }
+}
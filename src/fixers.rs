@@ -2,20 +2,26 @@ use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use dictionary::Dictionary;
+
 use crate::dblp::{
     Record,
-    record::{Crossref, External},
+    record::{Crossref, External, Person},
 };
 
-mod names;
-mod unicode;
+pub mod casing;
+pub mod corrections;
+pub mod dictionary;
+pub mod math;
+pub mod names;
+pub mod pipeline;
+pub mod unicode;
 
 lazy_static! {
     static ref RANGE_PATTERN: Regex = Regex::new(r"(\d)-(\d)").unwrap();
-    static ref AUTHOR_NUM_PATTERN: Regex = Regex::new(r" \d\d\d\d$").unwrap();
+    static ref AUTHOR_NUM_PATTERN: Regex = Regex::new(r" (\d\d\d\d)$").unwrap();
     static ref HYPHENATED_WORD_PATTERN: Regex = Regex::new(r"[\w\d-]+").unwrap();
     static ref DATE_RANGE_PATTERN: Regex = Regex::new(r"(\d)-(\d)|(\d\s)-(\sJanuary|\sFebruary|\sMarch|\sApril|\sMay|\sJune|\sJuly|\sAugust|\sSeptember|\sOctober|\sNovember|\sDecember)").unwrap();
-    static ref PROPER_NOUNS_PATTERN: Regex = Regex::new(r"(Bool(ean)?|Pareto|Slide\\\&Drill|Seesaw)").unwrap();
     static ref CAPITAL_AFTER_COLON: Regex = Regex::new(r":\s+[A-Z]").unwrap();
 }
 
@@ -33,16 +39,23 @@ pub fn page_range(rec: &mut Record) {
     }
 }
 
+/// Moves the DBLP disambiguation number from the name to [`Person::number`]
+fn strip_author_num(person: &mut Person) {
+    let Some(caps) = AUTHOR_NUM_PATTERN.captures(&person.name) else {
+        return;
+    };
+    person.number = Some(String::from(&caps[1]));
+    let end = caps.get(0).unwrap().start();
+    person.name.truncate(end);
+}
+
 pub fn author_num(rec: &mut Record) {
     match rec {
         Record::Article { author, .. }
         | Record::Inproceedings { author, .. }
         | Record::Book { author, .. }
         | Record::Incollection { author, .. } => {
-            for author in author.iter_mut() {
-                let rep = AUTHOR_NUM_PATTERN.replace(author, "");
-                *author = rep.to_string();
-            }
+            author.iter_mut().for_each(strip_author_num);
         }
         _ => {}
     }
@@ -57,10 +70,7 @@ pub fn author_num(rec: &mut Record) {
             crossref: Crossref::Resolved { editor, .. },
             ..
         } => {
-            for editor in editor.iter_mut() {
-                let rep = AUTHOR_NUM_PATTERN.replace(editor, "");
-                *editor = rep.to_string();
-            }
+            editor.iter_mut().for_each(strip_author_num);
         }
         _ => {}
     }
@@ -75,7 +85,7 @@ fn all_strings(rec: &mut Record, apply: impl Fn(&mut String)) {
             ..
         } => {
             for author in author.iter_mut() {
-                apply(author);
+                apply(&mut author.name);
             }
             apply(title);
             apply(journal);
@@ -88,7 +98,7 @@ fn all_strings(rec: &mut Record, apply: impl Fn(&mut String)) {
             ..
         } => {
             for editor in editor.iter_mut() {
-                apply(editor);
+                apply(&mut editor.name);
             }
             apply(title);
             if let Some(series) = series {
@@ -106,7 +116,7 @@ fn all_strings(rec: &mut Record, apply: impl Fn(&mut String)) {
             ..
         } => {
             for author in author.iter_mut() {
-                apply(author);
+                apply(&mut author.name);
             }
             apply(title);
             apply(booktitle);
@@ -118,7 +128,7 @@ fn all_strings(rec: &mut Record, apply: impl Fn(&mut String)) {
             } = crossref
             {
                 for editor in editor.iter_mut() {
-                    apply(editor);
+                    apply(&mut editor.name);
                 }
                 if let Some(series) = series {
                     apply(series);
@@ -136,7 +146,7 @@ fn all_strings(rec: &mut Record, apply: impl Fn(&mut String)) {
             ..
         } => {
             for author in author.iter_mut() {
-                apply(author);
+                apply(&mut author.name);
             }
             apply(title);
             apply(booktitle);
@@ -148,7 +158,7 @@ fn all_strings(rec: &mut Record, apply: impl Fn(&mut String)) {
             } = crossref
             {
                 for editor in editor.iter_mut() {
-                    apply(editor);
+                    apply(&mut editor.name);
                 }
                 if let Some(series) = series {
                     apply(series);
@@ -167,10 +177,10 @@ fn all_strings(rec: &mut Record, apply: impl Fn(&mut String)) {
             ..
         } => {
             for author in author.iter_mut() {
-                apply(author);
+                apply(&mut author.name);
             }
             for editor in editor.iter_mut() {
-                apply(editor);
+                apply(&mut editor.name);
             }
             apply(title);
             if let Some(series) = series {
@@ -187,7 +197,7 @@ fn all_strings(rec: &mut Record, apply: impl Fn(&mut String)) {
             ..
         } => {
             for author in author.iter_mut() {
-                apply(author);
+                apply(&mut author.name);
             }
             apply(title);
             if let Some(publisher) = publisher {
@@ -197,9 +207,21 @@ fn all_strings(rec: &mut Record, apply: impl Fn(&mut String)) {
     }
 }
 
+/// Escapes characters with special meaning in LaTeX, writing the inline math marked by the math
+/// fixer as `$...$`
 fn escape_latex_chars(input: &mut String) {
+    let math = math::marked(input);
     let mut out: Option<String> = None;
-    for (idx, char) in input.chars().enumerate() {
+    for (idx, char) in input.char_indices() {
+        if math::in_math(&math, idx) {
+            if char == math::DELIM {
+                out.get_or_insert_with(|| String::from(&input[..idx]))
+                    .push('$');
+            } else if let Some(out) = out.as_mut() {
+                out.push(char);
+            }
+            continue;
+        }
         let repl = match char {
             '#' => Some(r"\#"),
             '$' => Some(r"\$"),
@@ -232,10 +254,38 @@ fn escape_latex_chars(input: &mut String) {
     }
 }
 
+/// Wraps inline math in titles in `$...$`
+pub fn math(rec: &mut Record) {
+    let (Record::Article { title, .. }
+    | Record::Proceedings { title, .. }
+    | Record::Inproceedings { title, .. }
+    | Record::Book { title, .. }
+    | Record::Incollection { title, .. }
+    | Record::Misc { title, .. }) = rec;
+    math::fix(title);
+}
+
+/// Writes inline math that is still marked, e.g., because LaTeX escaping is disabled, as `$...$`
+pub fn unmark_math(rec: &mut Record) {
+    let (Record::Article { title, .. }
+    | Record::Proceedings { title, .. }
+    | Record::Inproceedings { title, .. }
+    | Record::Book { title, .. }
+    | Record::Incollection { title, .. }
+    | Record::Misc { title, .. }) = rec;
+    math::unmark(title);
+}
+
 pub fn escape_latex(rec: &mut Record) {
     all_strings(rec, escape_latex_chars)
 }
 
+/// Converts LaTeX commands for special characters back to unicode, e.g., for records parsed from
+/// an existing BibTeX file when unicode is kept
+pub fn latex_to_unicode(rec: &mut Record) {
+    all_strings(rec, unicode::to_unicode);
+}
+
 pub fn unicode(rec: &mut Record) {
     all_strings(rec, unicode::replace);
     match rec {
@@ -254,14 +304,15 @@ pub fn unicode(rec: &mut Record) {
     }
 }
 
-pub fn names(rec: &mut Record) {
+/// Writes names as `Last, First`, using the name overrides from the dictionary
+pub fn names(rec: &mut Record, dict: &Dictionary) {
     match rec {
         Record::Article { author, .. }
         | Record::Inproceedings { author, .. }
         | Record::Book { author, .. }
         | Record::Incollection { author, .. } => {
             for author in author {
-                names::fix(author);
+                names::fix(&mut author.name, author.pid.as_deref(), dict);
             }
         }
         _ => {}
@@ -278,59 +329,54 @@ pub fn names(rec: &mut Record) {
             ..
         } => {
             for editor in editor {
-                names::fix(editor);
+                names::fix(&mut editor.name, editor.pid.as_deref(), dict);
             }
         }
         _ => {}
     }
 }
 
-fn fix_proper_nouns(string: &mut String) {
-    let mut changed = None;
-    let mut offset = 0;
-    for matched in PROPER_NOUNS_PATTERN.find_iter(string) {
-        let start = matched.start() + offset;
-        let end = matched.end() + offset;
-        let changed = changed.get_or_insert_with(|| string.clone());
-        // wrap in braces
-        changed.insert(end, '}');
-        changed.insert(start, '{');
-        offset += 2;
-    }
-    if let Some(changed) = changed {
-        *string = changed;
-    }
-}
-
-pub fn proper_nouns(rec: &mut Record) {
+/// Wraps proper nouns from the dictionary in curly braces
+pub fn proper_nouns(rec: &mut Record, dict: &Dictionary) {
     let (Record::Article { title, .. }
     | Record::Proceedings { title, .. }
     | Record::Inproceedings { title, .. }
     | Record::Book { title, .. }
     | Record::Incollection { title, .. }
     | Record::Misc { title, .. }) = rec;
-    fix_proper_nouns(title);
+    dict.protect(title);
     if let Record::Inproceedings { booktitle, .. } | Record::Incollection { booktitle, .. } = rec {
-        fix_proper_nouns(booktitle);
+        dict.protect(booktitle);
     }
 }
 
 /// Wraps acronyms such as `SAT` of `MaxSAT` in curly braces
-fn fix_acronyms(string: &mut String) {
+///
+/// Words that are already braced or excluded by the dictionary are left as is.
+fn fix_acronyms(string: &mut String, dict: &Dictionary) {
+    let math = math::ranges(string);
     let mut changed = None;
     let mut offset = 0;
     for matched in HYPHENATED_WORD_PATTERN.find_iter(string) {
+        if math::in_math(&math, matched.start()) {
+            continue;
+        }
+        // a dash after inline math, e.g., in `$k$-CNF`, is not part of the word
+        let (matched_start, matched) = match matched.as_str().strip_prefix('-') {
+            Some(word) if string[..matched.start()].ends_with('$') => (matched.start() + 1, word),
+            _ => (matched.start(), matched.as_str()),
+        };
+        let matched_end = matched_start + matched.len();
         // Acronym cases:
         // 1. has more than one upper case and not all of them are after a dash
         // 2. starts with lower case, but contains upper case
         // 3. contains an upper case letter and a numeric digit
         let first_upper = matched
-            .as_str()
             .chars()
             .next()
             .map(char::is_uppercase)
             .unwrap_or(false);
-        let (n_upper, n_upper_after_dash, n_nums) = matched.as_str().chars().tuple_windows().fold(
+        let (n_upper, n_upper_after_dash, n_nums) = matched.chars().tuple_windows().fold(
             (if first_upper { 1 } else { 0 }, 0, 0),
             |(total, after_dash, nums), (first, second)| {
                 (
@@ -340,16 +386,21 @@ fn fix_acronyms(string: &mut String) {
                 )
             },
         );
-        let start = matched.start() + offset;
-        let mut end = matched.end() + offset;
+        let start = matched_start + offset;
+        let mut end = matched_end + offset;
         // Special exceptions:
         // - `anything-based` (e.g., SAT-based), the `-based` will not be included in the acronym
         if matches!(
-            matched.as_str().rsplit_once('-'),
+            matched.rsplit_once('-'),
             Some((_, "based")) | Some((_, "Based"))
         ) {
             end -= 6;
         }
+        if dictionary::is_braced(string, start - offset, end - offset)
+            || dict.is_not_acronym(&string[start - offset..end - offset])
+        {
+            continue;
+        }
         if n_upper - n_upper_after_dash > 1
             || (n_upper > 0 && n_nums > 0)
             || (!first_upper && n_upper > 0)
@@ -366,16 +417,30 @@ fn fix_acronyms(string: &mut String) {
     }
 }
 
-pub fn acronyms(rec: &mut Record) {
+pub fn acronyms(rec: &mut Record, dict: &Dictionary) {
     let (Record::Article { title, .. }
     | Record::Proceedings { title, .. }
     | Record::Inproceedings { title, .. }
     | Record::Book { title, .. }
     | Record::Incollection { title, .. }
     | Record::Misc { title, .. }) = rec;
-    fix_acronyms(title);
+    fix_acronyms(title, dict);
     if let Record::Inproceedings { booktitle, .. } | Record::Incollection { booktitle, .. } = rec {
-        fix_acronyms(booktitle);
+        fix_acronyms(booktitle, dict);
+    }
+}
+
+/// Converts titles and booktitles to the given case
+pub fn casing(rec: &mut Record, case: casing::Case) {
+    let (Record::Article { title, .. }
+    | Record::Proceedings { title, .. }
+    | Record::Inproceedings { title, .. }
+    | Record::Book { title, .. }
+    | Record::Incollection { title, .. }
+    | Record::Misc { title, .. }) = rec;
+    casing::convert(title, case);
+    if let Record::Inproceedings { booktitle, .. } | Record::Incollection { booktitle, .. } = rec {
+        casing::convert(booktitle, case);
     }
 }
 
@@ -490,81 +555,36 @@ pub fn dashes(rec: &mut Record) {
     }
 }
 
-pub fn expand_booktitle(rec: &mut Record, crossref: &Record) {
+/// Sets the booktitle of a crossref-style record from the title of the crossref, in the given
+/// venue style
+pub fn expand_booktitle(rec: &mut Record, crossref: &Record, style: crate::venues::Style) {
     match rec {
         Record::Inproceedings {
+            key,
             booktitle,
+            year,
             crossref: Crossref::Key(_),
             ..
         }
         | Record::Incollection {
+            key,
             booktitle,
+            year,
             crossref: Crossref::Key(_),
             ..
         } => {
-            *booktitle = crossref.title().to_owned();
+            *booktitle = crate::venues::booktitle(
+                style,
+                crate::dblp::record::venue_key(key),
+                booktitle,
+                crossref.title(),
+                *year,
+            );
         }
         _ => panic!("got non-crossref record"),
     }
 }
 
-/// Manual fixer for mistakes in DBLP data
-pub fn manually_correct(rec: &mut Record) {
-    // Mistake introduced in the metadata in the final editing process
-    if rec.key() == "conf/tacas/JabsBBJ25"
-        && let Record::Inproceedings { title, .. } = rec
-    {
-        *title =
-            String::from("Certifying Pareto Optimality in Multi-objective Maximum Satisfiability");
-    }
-    // Smallcaps acronyms not properly in DBLP metadata
-    if rec.key() == "conf/sat/DaviesB13"
-        && let Record::Inproceedings { title, .. } = rec
-    {
-        *title = String::from("Exploiting the Power of {MIP} Solvers in {MAXSAT}");
-    }
-    // Incorrect year for JELIA proceedings
-    if rec.key().starts_with("conf/jelia/2025")
-        && let Record::Proceedings { year, .. } = rec
-    {
-        *year = 2025;
-    }
-    // Custom entries used in thesis
-    if rec.key() == "conf/cp/JabsBIJ23"
-        && let Record::Inproceedings { usera, .. } = rec
-    {
-        *usera = Some(String::from(
-            "Proceedings of International Conference on Principles and Practice of Constraint Programming, {CP} 2023",
-        ));
-    }
-    if rec.key() == "conf/cpaior/JabsBJ24"
-        && let Record::Inproceedings { usera, .. } = rec
-    {
-        *usera = Some(String::from(
-            "Proceedings of Integration of Constraint Programming, Artificial Intelligence, and Operations Research, {CPAIOR} 2024",
-        ));
-    }
-    if rec.key() == "conf/tacas/JabsBBJ25"
-        && let Record::Inproceedings { usera, .. } = rec
-    {
-        *usera = Some(String::from(
-            "Proceedings of Tools and Algorithms for the Construction and Analysis of Systems, {TACAS} 2025",
-        ));
-    }
-    if rec.key() == "conf/jelia/JabsBJ25"
-        && let Record::Inproceedings { usera, .. } = rec
-    {
-        *usera = Some(String::from(
-            "Logics in Artificial Intelligence, {JELIA} 2025",
-        ));
-    }
-    if rec.key() == "conf/ijcai/ArgelichLS09"
-        && let Record::Inproceedings { title, .. } = rec
-    {
-        *title = title.replace("Problemse", "Problems");
-    }
-}
-
 /// Removes all but one external link
 ///
 /// If a DOI is present, the first one is used, otherwise the first URL is used
@@ -592,69 +612,125 @@ pub fn single_external(rec: &mut Record) {
 
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn author_num() {
+        use crate::dblp::record::{Crossref, Person};
+
+        let mut rec = super::Record::Incollection {
+            key: String::from("series/faia/0001LM21"),
+            author: vec![
+                Person::from("João Marques-Silva 0001"),
+                Person::from("Inês Lynce"),
+            ],
+            title: String::from("Conflict-Driven Clause Learning SAT Solvers"),
+            booktitle: String::from("Handbook of Satisfiability"),
+            year: 2021,
+            pages: None,
+            external: vec![],
+            crossref: Crossref::Key(String::from("series/faia/336")),
+        };
+        super::author_num(&mut rec);
+        let super::Record::Incollection { author, .. } = rec else {
+            unreachable!()
+        };
+        assert_eq!(author[0].name, "João Marques-Silva");
+        assert_eq!(author[0].number.as_deref(), Some("0001"));
+        assert_eq!(author[1].name, "Inês Lynce");
+        assert_eq!(author[1].number, None);
+    }
+
+    #[test]
+    fn escape_latex() {
+        let mut text = String::from("From $5 to $10: 50% Off");
+        super::escape_latex_chars(&mut text);
+        assert_eq!(text, r"From \$5 to \$10: 50\% Off");
+
+        let mut text = String::from("Solving k-SAT for $5 & x_1");
+        super::math::fix(&mut text);
+        super::escape_latex_chars(&mut text);
+        assert_eq!(text, r"Solving $k$-SAT for \$5 \& $x_1$");
+    }
+
     #[test]
     fn acronyms() {
+        let dict = super::Dictionary::builtin();
+
         let mut text = String::from("SAT is an Acronym");
-        super::fix_acronyms(&mut text);
+        super::fix_acronyms(&mut text, &dict);
         assert_eq!(text, "{SAT} is an Acronym");
 
         let mut text = String::from("Another Acronym is MaxSAT");
-        super::fix_acronyms(&mut text);
+        super::fix_acronyms(&mut text, &dict);
         assert_eq!(text, "Another Acronym is {MaxSAT}");
 
         let mut text = String::from("With SAT and MaxSAT we have two acronyms");
-        super::fix_acronyms(&mut text);
+        super::fix_acronyms(&mut text, &dict);
         assert_eq!(text, "With {SAT} and {MaxSAT} we have two acronyms");
 
         let mut text = String::from("Some people write Max-SAT");
-        super::fix_acronyms(&mut text);
+        super::fix_acronyms(&mut text, &dict);
         assert_eq!(text, "Some people write {Max-SAT}");
 
         let mut text = String::from("MaxSAT-based bi-objective optimization");
-        super::fix_acronyms(&mut text);
+        super::fix_acronyms(&mut text, &dict);
         assert_eq!(text, "{MaxSAT}-based bi-objective optimization");
 
         let mut text =
             String::from("Using Small MUSes to Explain How to Solve Pen and Paper Puzzles.");
-        super::fix_acronyms(&mut text);
+        super::fix_acronyms(&mut text, &dict);
         assert_eq!(
             text,
             "Using Small {MUSes} to Explain How to Solve Pen and Paper Puzzles."
         );
 
         let mut text = String::from("Thirty-First should not be an ancronym");
-        super::fix_acronyms(&mut text);
+        super::fix_acronyms(&mut text, &dict);
         assert_eq!(text, "Thirty-First should not be an ancronym");
 
         let mut text = String::from("big-M should be an acronym");
-        super::fix_acronyms(&mut text);
+        super::fix_acronyms(&mut text, &dict);
         assert_eq!(text, "{big-M} should be an acronym");
 
         let mut text = String::from("SAT-Based and MaxSAT-Based are special exceptions");
-        super::fix_acronyms(&mut text);
+        super::fix_acronyms(&mut text, &dict);
         assert_eq!(
             text,
             "{SAT}-Based and {MaxSAT}-Based are special exceptions"
         );
 
         let mut text = String::from("The Sat4j library, release 2.2.");
-        super::fix_acronyms(&mut text);
+        super::fix_acronyms(&mut text, &dict);
         assert_eq!(text, "The {Sat4j} library, release 2.2.");
 
         let mut text = String::from("A number is not an acronym 2024");
-        super::fix_acronyms(&mut text);
+        super::fix_acronyms(&mut text, &dict);
         assert_eq!(text, "A number is not an acronym 2024");
+
+        let mut text = String::from("Solving $k$-CNF with $O(n)$ MaxSAT Calls");
+        super::fix_acronyms(&mut text, &dict);
+        assert_eq!(text, "Solving $k$-{CNF} with $O(n)$ {MaxSAT} Calls");
+
+        let mut text = String::from("Already {SAT} braced");
+        super::fix_acronyms(&mut text, &dict);
+        assert_eq!(text, "Already {SAT} braced");
+
+        let dict = super::Dictionary::parse(r#"not_acronyms = ["NP-Hard"]"#).unwrap();
+        let mut text = String::from("MaxSAT is NP-Hard");
+        super::fix_acronyms(&mut text, &dict);
+        assert_eq!(text, "{MaxSAT} is NP-Hard");
     }
 
     #[test]
     fn proper_nouns() {
+        let dict = super::Dictionary::builtin();
+
         let mut text = String::from("Translating Pseudo-Boolean Constraints into SAT");
-        super::fix_proper_nouns(&mut text);
+        dict.protect(&mut text);
         assert_eq!(text, "Translating Pseudo-{Boolean} Constraints into SAT");
 
         let mut text =
             String::from("A Support-Based Algorithm for the Bi-Objective Pareto Constraint");
-        super::fix_proper_nouns(&mut text);
+        dict.protect(&mut text);
         assert_eq!(
             text,
             "A Support-Based Algorithm for the Bi-Objective {Pareto} Constraint"
@@ -0,0 +1,222 @@
+//! # Title Casing
+//!
+//! Converts titles to Title Case or sentence case. Text in braces, inline math and LaTeX commands
+//! are never changed, so this relies on the fixers that protect acronyms, proper nouns and
+//! capitals after colons to run first.
+
+/// Words that are not capitalized in Title Case, unless they are the first or last word
+const SMALL_WORDS: [&str; 25] = [
+    "a", "an", "and", "as", "at", "but", "by", "for", "from", "in", "into", "nor", "of", "on",
+    "onto", "or", "per", "so", "than", "the", "to", "up", "via", "vs", "with",
+];
+
+#[derive(
+    clap::ValueEnum,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Case {
+    /// Capitalize all words except small words such as "of" or "and"
+    #[default]
+    Title,
+    /// Only capitalize the first word and the first word after a colon
+    Sentence,
+}
+
+/// Splits the string at the given character outside of braces and math, keeping the separators
+fn split_unbraced(string: &str, is_sep: impl Fn(char) -> bool) -> Vec<&str> {
+    let math = super::math::ranges(string);
+    let mut parts = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (idx, ch) in string.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ch if depth == 0 && is_sep(ch) && !super::math::in_math(&math, idx) => {
+                parts.push(&string[start..idx]);
+                parts.push(&string[idx..idx + ch.len_utf8()]);
+                start = idx + ch.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&string[start..]);
+    parts
+}
+
+/// Changes the case of the unprotected letters of a word part
+///
+/// If `capitalize` is set, the first letter is upper-cased, unless the part starts with protected
+/// text. If `lower` is set, all other letters are lower-cased.
+fn recase(part: &str, capitalize: bool, lower: bool) -> String {
+    let math = super::math::ranges(part);
+    let mut out = String::with_capacity(part.len());
+    let mut depth = 0usize;
+    let mut first = true;
+    let mut chars = part.char_indices().peekable();
+    while let Some((idx, ch)) = chars.next() {
+        if super::math::in_math(&math, idx) {
+            first = false;
+            out.push(ch);
+            continue;
+        }
+        match ch {
+            '{' => {
+                depth += 1;
+                first = false;
+                out.push(ch);
+            }
+            '}' => {
+                depth = depth.saturating_sub(1);
+                out.push(ch);
+            }
+            _ if depth > 0 => out.push(ch),
+            '\\' => {
+                // LaTeX commands are protected
+                first = false;
+                out.push(ch);
+                if chars.peek().is_some_and(|(_, ch)| ch.is_alphabetic()) {
+                    while let Some((_, ch)) = chars.next_if(|(_, ch)| ch.is_alphabetic()) {
+                        out.push(ch);
+                    }
+                } else if let Some((_, ch)) = chars.next() {
+                    out.push(ch);
+                }
+            }
+            ch if ch.is_alphabetic() => {
+                if first && capitalize {
+                    out.extend(ch.to_uppercase());
+                } else if lower {
+                    out.extend(ch.to_lowercase());
+                } else {
+                    out.push(ch);
+                }
+                first = false;
+            }
+            ch => {
+                if ch.is_numeric() {
+                    first = false;
+                }
+                out.push(ch);
+            }
+        }
+    }
+    out
+}
+
+/// Converts the string to the given case, leaving protected text unchanged
+pub fn convert(string: &mut String, case: Case) {
+    let tokens = split_unbraced(string, char::is_whitespace);
+    let n_words = tokens
+        .iter()
+        .filter(|token| !token.trim().is_empty())
+        .count();
+    let mut out = String::with_capacity(string.len());
+    let mut word_idx = 0;
+    let mut after_colon = false;
+    for token in tokens {
+        if token.trim().is_empty() {
+            out.push_str(token);
+            continue;
+        }
+        let starts_phrase = word_idx == 0 || after_colon;
+        let is_last = word_idx + 1 == n_words;
+        for (part_idx, part) in split_unbraced(token, |ch| ch == '-')
+            .into_iter()
+            .enumerate()
+        {
+            if part == "-" {
+                out.push_str(part);
+                continue;
+            }
+            let first_part = part_idx == 0;
+            let cased = match case {
+                Case::Title => {
+                    let small = SMALL_WORDS.contains(&part.to_lowercase().as_str());
+                    if small && !(starts_phrase && first_part) && !is_last {
+                        recase(part, false, true)
+                    } else {
+                        recase(part, true, false)
+                    }
+                }
+                Case::Sentence => recase(part, starts_phrase && first_part, true),
+            };
+            out.push_str(&cased);
+        }
+        after_colon = token.ends_with(':');
+        word_idx += 1;
+    }
+    *string = out;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Case, convert};
+
+    #[test]
+    fn title_case() {
+        let cases = [
+            (
+                "preprocessing in {SAT}-based multi-objective combinatorial optimization",
+                "Preprocessing in {SAT}-Based Multi-Objective Combinatorial Optimization",
+            ),
+            (
+                "The Seesaw Algorithm: {F}unction Optimization Using Implicit Hitting Sets",
+                "The Seesaw Algorithm: {F}unction Optimization Using Implicit Hitting Sets",
+            ),
+            (
+                "A Survey Of The State-Of-The-Art In {MaxSAT}",
+                "A Survey of the State-of-the-Art in {MaxSAT}",
+            ),
+            ("what is it good for", "What Is It Good For"),
+            (
+                "learning with {Bayesian} networks \\& trees",
+                "Learning with {Bayesian} Networks \\& Trees",
+            ),
+        ];
+        for (input, expected) in cases {
+            let mut text = String::from(input);
+            convert(&mut text, Case::Title);
+            assert_eq!(text, expected);
+        }
+    }
+
+    #[test]
+    fn sentence_case() {
+        let cases = [
+            (
+                "Preprocessing in {SAT}-Based Multi-Objective Combinatorial Optimization",
+                "Preprocessing in {SAT}-based multi-objective combinatorial optimization",
+            ),
+            (
+                "The Seesaw Algorithm: {F}unction Optimization Using Implicit Hitting Sets",
+                "The seesaw algorithm: {F}unction optimization using implicit hitting sets",
+            ),
+            (
+                "Certifying {Pareto} Optimality: Multi-Objective {MaxSAT}",
+                "Certifying {Pareto} optimality: Multi-objective {MaxSAT}",
+            ),
+            (
+                "An $O(n \\log n)$ Algorithm for $k$-{SAT}",
+                "An $O(n \\log n)$ algorithm for $k$-{SAT}",
+            ),
+            (
+                "{MIP} Solvers in {MAXSAT} {\\textemdash} A Study",
+                "{MIP} solvers in {MAXSAT} {\\textemdash} a study",
+            ),
+        ];
+        for (input, expected) in cases {
+            let mut text = String::from(input);
+            convert(&mut text, Case::Sentence);
+            assert_eq!(text, expected);
+        }
+    }
+}
@@ -0,0 +1,368 @@
+//! # Manual Corrections
+//!
+//! Corrections of individual records that no heuristic fixer can make, e.g., mistakes in the DBLP
+//! metadata or custom fields. A built-in set of corrections for known mistakes in DBLP is always
+//! applied, user corrections are read from a TOML file and applied afterwards.
+//!
+//! Each correction matches DBLP keys without prefix, where `*` matches any sequence of characters
+//! and `?` any single character. It can set fields, which also adds optional fields like `usera`,
+//! and replace regular expression matches in fields:
+//!
+//! ```toml
+//! [[correction]]
+//! key = "conf/cp/JabsBIJ23"
+//! set.usera = "Proceedings of Principles and Practice of Constraint Programming, {CP} 2023"
+//!
+//! [[correction]]
+//! key = "conf/jelia/2025*"
+//! set.year = 2025
+//!
+//! [[correction]]
+//! key = "conf/ijcai/ArgelichLS09"
+//! replace = [{ field = "title", pattern = "Problemse", with = "Problems" }]
+//! ```
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+
+use super::pipeline::FixReport;
+use crate::dblp::Record;
+
+const BUILTIN: &str = include_str!("corrections.toml");
+
+/// The fields that corrections can modify
+const FIELDS: [&str; 9] = [
+    "title",
+    "journal",
+    "booktitle",
+    "year",
+    "pages",
+    "volume",
+    "series",
+    "publisher",
+    "usera",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid corrections file: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Invalid regular expression: {0}")]
+    Regex(#[from] regex::Error),
+    #[error("Unknown field `{0}` in correction for `{1}`")]
+    UnknownField(String, String),
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    #[serde(default)]
+    correction: Vec<RawCorrection>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawCorrection {
+    key: String,
+    #[serde(default)]
+    set: BTreeMap<String, Value>,
+    #[serde(default)]
+    replace: Vec<RawReplacement>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawReplacement {
+    field: String,
+    pattern: String,
+    with: String,
+}
+
+/// A value to set a field to
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(untagged)]
+pub enum Value {
+    Number(u32),
+    Text(String),
+}
+
+#[derive(Clone, Debug)]
+struct Replacement {
+    field: String,
+    pattern: Regex,
+    with: String,
+}
+
+#[derive(Clone, Debug)]
+struct Correction {
+    /// The key glob as written in the file, for warnings
+    glob: String,
+    key: Regex,
+    set: BTreeMap<String, Value>,
+    replace: Vec<Replacement>,
+}
+
+/// A list of corrections, applied in order
+#[derive(Clone, Debug, Default)]
+pub struct Corrections {
+    corrections: Vec<Correction>,
+}
+
+/// Mutable access to a field of a record
+enum Field<'a> {
+    Text(&'a mut String),
+    Optional(&'a mut Option<String>),
+    Year(&'a mut u32),
+}
+
+fn field<'a>(rec: &'a mut Record, name: &str) -> Option<Field<'a>> {
+    Some(match (rec, name) {
+        (
+            Record::Article { title, .. }
+            | Record::Proceedings { title, .. }
+            | Record::Inproceedings { title, .. }
+            | Record::Book { title, .. }
+            | Record::Incollection { title, .. }
+            | Record::Misc { title, .. },
+            "title",
+        ) => Field::Text(title),
+        (
+            Record::Article { year, .. }
+            | Record::Proceedings { year, .. }
+            | Record::Inproceedings { year, .. }
+            | Record::Book { year, .. }
+            | Record::Incollection { year, .. }
+            | Record::Misc { year, .. },
+            "year",
+        ) => Field::Year(year),
+        (Record::Article { journal, .. }, "journal") => Field::Text(journal),
+        (
+            Record::Inproceedings { booktitle, .. } | Record::Incollection { booktitle, .. },
+            "booktitle",
+        ) => Field::Text(booktitle),
+        (
+            Record::Article { pages, .. }
+            | Record::Inproceedings { pages, .. }
+            | Record::Incollection { pages, .. },
+            "pages",
+        ) => Field::Optional(pages),
+        (
+            Record::Article { volume, .. }
+            | Record::Proceedings { volume, .. }
+            | Record::Book { volume, .. },
+            "volume",
+        ) => Field::Optional(volume),
+        (Record::Proceedings { series, .. } | Record::Book { series, .. }, "series") => {
+            Field::Optional(series)
+        }
+        (
+            Record::Proceedings { publisher, .. }
+            | Record::Book { publisher, .. }
+            | Record::Misc { publisher, .. },
+            "publisher",
+        ) => Field::Optional(publisher),
+        (Record::Inproceedings { usera, .. }, "usera") => Field::Optional(usera),
+        _ => return None,
+    })
+}
+
+/// Converts a key glob into an anchored regular expression
+fn glob_regex(glob: &str) -> Result<Regex, regex::Error> {
+    let mut pattern = String::from("^");
+    for ch in glob.chars() {
+        match ch {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            ch => pattern.push_str(&regex::escape(&String::from(ch))),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern)
+}
+
+impl Corrections {
+    /// The built-in corrections of known mistakes in DBLP
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN).expect("built-in corrections must be valid")
+    }
+
+    pub fn read<P: AsRef<camino::Utf8Path>>(path: P) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path.as_ref())?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let file: File = toml::from_str(content)?;
+        let mut corrections = Vec::with_capacity(file.correction.len());
+        for raw in file.correction {
+            for name in raw
+                .set
+                .keys()
+                .chain(raw.replace.iter().map(|rep| &rep.field))
+            {
+                if !FIELDS.contains(&name.as_str()) {
+                    return Err(Error::UnknownField(name.clone(), raw.key));
+                }
+            }
+            let replace = raw
+                .replace
+                .into_iter()
+                .map(|rep| {
+                    Ok(Replacement {
+                        field: rep.field,
+                        pattern: Regex::new(&rep.pattern)?,
+                        with: rep.with,
+                    })
+                })
+                .collect::<Result<_, regex::Error>>()?;
+            corrections.push(Correction {
+                key: glob_regex(&raw.key)?,
+                glob: raw.key,
+                set: raw.set,
+                replace,
+            });
+        }
+        Ok(Self { corrections })
+    }
+
+    /// Appends other corrections, which are applied after the existing ones
+    pub fn extend(&mut self, other: Self) -> &mut Self {
+        self.corrections.extend(other.corrections);
+        self
+    }
+
+    /// Applies all corrections matching the record
+    pub fn apply(&self, rec: &mut Record, report: &mut FixReport) {
+        for correction in &self.corrections {
+            if !correction.key.is_match(rec.key()) {
+                continue;
+            }
+            for (name, value) in &correction.set {
+                let Some(field) = field(rec, name) else {
+                    report.note(format!(
+                        "`{}` has no field `{name}`, ignoring correction `{}`",
+                        rec.key(),
+                        correction.glob
+                    ));
+                    continue;
+                };
+                match (field, value) {
+                    (Field::Text(field), Value::Text(value)) => *field = value.clone(),
+                    (Field::Optional(field), Value::Text(value)) => *field = Some(value.clone()),
+                    (Field::Text(field), Value::Number(value)) => *field = value.to_string(),
+                    (Field::Optional(field), Value::Number(value)) => {
+                        *field = Some(value.to_string())
+                    }
+                    (Field::Year(field), Value::Number(value)) => *field = *value,
+                    (Field::Year(field), Value::Text(value)) => match value.parse() {
+                        Ok(value) => *field = value,
+                        Err(_) => {
+                            report.note(format!(
+                                "invalid year `{value}` in correction `{}`",
+                                correction.glob
+                            ));
+                        }
+                    },
+                }
+            }
+            for rep in &correction.replace {
+                let text = match field(rec, &rep.field) {
+                    Some(Field::Text(text)) => text,
+                    Some(Field::Optional(Some(text))) => text,
+                    _ => continue,
+                };
+                *text = rep.pattern.replace_all(text, rep.with.as_str()).to_string();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dblp::{
+        Record,
+        record::{Crossref, Person},
+    };
+
+    fn inproceedings(key: &str) -> Record {
+        Record::Inproceedings {
+            key: String::from(key),
+            author: vec![Person::from("Argelich, Josep")],
+            title: String::from("Solving Problemse with {MaxSAT}"),
+            booktitle: String::from("IJCAI"),
+            year: 2009,
+            pages: None,
+            external: vec![],
+            crossref: Crossref::Key(String::from("conf/ijcai/2009")),
+            usera: None,
+        }
+    }
+
+    #[test]
+    fn apply() {
+        let corrections = super::Corrections::parse(
+            r#"
+[[correction]]
+key = "conf/ijcai/*"
+set.usera = "International Joint Conference on Artificial Intelligence, {IJCAI}"
+set.year = "2010"
+
+[[correction]]
+key = "conf/ijcai/ArgelichLS0?"
+replace = [{ field = "title", pattern = "Problem(s)e", with = "Problem$1" }]
+set.pages = "1--10"
+"#,
+        )
+        .unwrap();
+
+        let mut rec = inproceedings("conf/ijcai/ArgelichLS09");
+        corrections.apply(&mut rec, &mut Default::default());
+        let Record::Inproceedings {
+            title,
+            year,
+            pages,
+            usera,
+            ..
+        } = rec
+        else {
+            unreachable!()
+        };
+        assert_eq!(title, "Solving Problems with {MaxSAT}");
+        assert_eq!(year, 2010);
+        assert_eq!(pages.as_deref(), Some("1--10"));
+        assert_eq!(
+            usera.as_deref(),
+            Some("International Joint Conference on Artificial Intelligence, {IJCAI}")
+        );
+
+        let mut rec = inproceedings("conf/aaai/ArgelichLS09");
+        corrections.apply(&mut rec, &mut Default::default());
+        let Record::Inproceedings { title, usera, .. } = rec else {
+            unreachable!()
+        };
+        assert_eq!(title, "Solving Problemse with {MaxSAT}");
+        assert_eq!(usera, None);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            super::Corrections::parse("[[correction]]\nkey = \"a\"\nset.authors = \"b\"\n"),
+            Err(super::Error::UnknownField(..))
+        ));
+        assert!(super::Corrections::parse("[[correction]]\nkey = \"a\"\nset = 1\n").is_err());
+    }
+
+    #[test]
+    fn builtin() {
+        let mut rec = inproceedings("conf/ijcai/ArgelichLS09");
+        super::Corrections::builtin().apply(&mut rec, &mut Default::default());
+        let Record::Inproceedings { title, .. } = rec else {
+            unreachable!()
+        };
+        assert_eq!(title, "Solving Problems with {MaxSAT}");
+    }
+}
@@ -0,0 +1,192 @@
+//! # Dictionaries
+//!
+//! Dictionaries of words and names that need special treatment. Words in `protect`, e.g., proper
+//! nouns like `Pareto`, are wrapped in braces so that bibliography styles don't change their
+//! case. Words in `not_acronyms` are never wrapped by the acronym heuristics. Entries in `names`
+//! override how the name of a person, given as written by DBLP or as DBLP person identifier, is
+//! split into its parts. A built-in dictionary is always
+//! used, user dictionaries are TOML files of the same format:
+//!
+//! ```toml
+//! protect = ["Gaussian", "Datalog"]
+//! not_acronyms = ["NP-Hard"]
+//!
+//! [names."Juan Carlos Martínez García"]
+//! given = "Juan Carlos"
+//! family = "Martínez García"
+//!
+//! [names."12/3456"]
+//! given = "Yi"
+//! family = "Wang Li"
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+
+use super::names::Name;
+
+const BUILTIN: &str = include_str!("dictionary.toml");
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid dictionary file: {0}")]
+    Toml(#[from] toml::de::Error),
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    #[serde(default)]
+    protect: Vec<String>,
+    #[serde(default)]
+    not_acronyms: Vec<String>,
+    #[serde(default)]
+    names: HashMap<String, Name>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Dictionary {
+    protect: HashSet<String>,
+    not_acronyms: HashSet<String>,
+    /// Names as written by DBLP or DBLP person identifiers and the parts of the name
+    names: HashMap<String, Name>,
+    /// Matches any of the words to protect on word boundaries
+    pattern: Option<Regex>,
+}
+
+/// Checks whether the range of the string is already wrapped in braces
+pub(super) fn is_braced(string: &str, start: usize, end: usize) -> bool {
+    string[..start].ends_with('{') && string[end..].starts_with('}')
+}
+
+impl Dictionary {
+    /// The built-in dictionary
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN).expect("built-in dictionary must be valid")
+    }
+
+    pub fn read<P: AsRef<camino::Utf8Path>>(path: P) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path.as_ref())?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let file: File = toml::from_str(content)?;
+        let mut dict = Self {
+            protect: file.protect.into_iter().collect(),
+            not_acronyms: file.not_acronyms.into_iter().collect(),
+            names: file.names,
+            pattern: None,
+        };
+        dict.compile();
+        Ok(dict)
+    }
+
+    /// Adds the words and names of another dictionary
+    pub fn extend(&mut self, other: Self) -> &mut Self {
+        self.protect.extend(other.protect);
+        self.not_acronyms.extend(other.not_acronyms);
+        self.names.extend(other.names);
+        self.compile();
+        self
+    }
+
+    fn compile(&mut self) {
+        if self.protect.is_empty() {
+            self.pattern = None;
+            return;
+        }
+        // longest words first, so that the longest match wins
+        let mut words: Vec<_> = self.protect.iter().collect();
+        words.sort_unstable_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+        let alternatives: Vec<_> = words.into_iter().map(|word| regex::escape(word)).collect();
+        self.pattern = Some(
+            Regex::new(&format!(r"\b(?:{})\b", alternatives.join("|")))
+                .expect("escaped words must form a valid pattern"),
+        );
+    }
+
+    /// Whether the word is protected by the dictionary
+    pub fn is_protected(&self, word: &str) -> bool {
+        self.protect.contains(word)
+    }
+
+    /// Whether the word must not be treated as an acronym
+    pub fn is_not_acronym(&self, word: &str) -> bool {
+        self.not_acronyms.contains(word) || self.is_protected(word)
+    }
+
+    /// The parts of a name, if the dictionary overrides them
+    pub fn name(&self, name: &str) -> Option<&Name> {
+        self.names.get(name)
+    }
+
+    /// Wraps all words to protect in the string in braces
+    pub fn protect(&self, string: &mut String) {
+        let Some(pattern) = &self.pattern else {
+            return;
+        };
+        let mut changed = None;
+        let mut offset = 0;
+        for matched in pattern.find_iter(string) {
+            if is_braced(string, matched.start(), matched.end()) {
+                continue;
+            }
+            let start = matched.start() + offset;
+            let end = matched.end() + offset;
+            let changed = changed.get_or_insert_with(|| string.clone());
+            // wrap in braces
+            changed.insert(end, '}');
+            changed.insert(start, '{');
+            offset += 2;
+        }
+        if let Some(changed) = changed {
+            *string = changed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn protect() {
+        let mut dict = super::Dictionary::builtin();
+        dict.extend(super::Dictionary::parse(r#"protect = ["Markov Chain", "Prolog"]"#).unwrap());
+
+        let mut text = String::from("Markov Chain Monte Carlo for Bayesian Prolog Programs");
+        dict.protect(&mut text);
+        assert_eq!(
+            text,
+            "{Markov Chain} {Monte Carlo} for {Bayesian} {Prolog} Programs"
+        );
+
+        // word boundaries
+        let mut text = String::from("Turingcomplete Booleans and Prologue");
+        dict.protect(&mut text);
+        assert_eq!(text, "Turingcomplete Booleans and Prologue");
+
+        // already protected
+        let mut text = String::from("A {Pareto} Front");
+        dict.protect(&mut text);
+        assert_eq!(text, "A {Pareto} Front");
+    }
+
+    #[test]
+    fn not_acronyms() {
+        let dict = super::Dictionary::parse(
+            r#"
+protect = ["LaTeX"]
+not_acronyms = ["NP-Hard"]
+"#,
+        )
+        .unwrap();
+        assert!(dict.is_not_acronym("NP-Hard"));
+        assert!(dict.is_not_acronym("LaTeX"));
+        assert!(!dict.is_not_acronym("SAT"));
+        assert!(dict.is_protected("LaTeX"));
+        assert!(!dict.is_protected("NP-Hard"));
+    }
+}
@@ -0,0 +1,332 @@
+//! # Inline Math Detection
+//!
+//! Recognizes inline math in titles, e.g., `O(n log n)`, `2^n`, `x_1`, `Σ2P` or `π-Calculus`,
+//! and wraps it in `$...$`. This needs to run before LaTeX escaping, which leaves inline math
+//! untouched. Until then, the inserted math is delimited by [`DELIM`] instead of `$`, so that
+//! literal dollar signs in titles are still escaped.
+//!
+//! Plain complexity classes like `NP` are left as text, they are protected by the acronym fixer.
+
+use std::ops::Range;
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+lazy_static! {
+    /// Asymptotic notation with up to one level of nested parentheses
+    static ref ASYMPTOTIC: Regex =
+        Regex::new(r"\b([OoΘΩω])\(((?:[^()]|\([^()]*\))*)\)").unwrap();
+    /// Classes of the polynomial hierarchy, e.g., `Σ2P`, `Σ_2^P` or `Π^p_2`
+    static ref HIERARCHY: Regex =
+        Regex::new(r"([ΣΠΔ])_?(\d+|[ik])?\^?([pP])_?(\d+|[ik])?\b").unwrap();
+    static ref POWER: Regex =
+        Regex::new(r"\b(\w+(?:\.\d+)?)\^(\{[^{}]*\}|\([^()]*\)|\w+(?:[./]\d+)?)").unwrap();
+    static ref SUBSCRIPT: Regex =
+        Regex::new(r"\b(\p{L})_(\{[^{}]*\}|\([^()]*\)|\w+)").unwrap();
+    /// Single Greek letters, e.g., in `π-Calculus`
+    static ref GREEK_LETTER: Regex = Regex::new(r"\b\p{Greek}\b").unwrap();
+    /// Single letter variables before a hyphenated all-caps word, e.g., in `k-CNF`, but not in
+    /// ordinary words such as `m-Commerce` or `t-SNE`
+    static ref VARIABLE_PREFIX: Regex = Regex::new(r"\b([kmn])-\p{Lu}[\p{Lu}\d]+\b").unwrap();
+    /// Single letter variables after a hyphen, e.g., in `Top-k`
+    static ref VARIABLE_SUFFIX: Regex = Regex::new(r"\p{L}-([kmn])\b").unwrap();
+    static ref FUNCTION: Regex = Regex::new(r"\b(log|ln|exp|min|max)\b").unwrap();
+    static ref TEXT_FUNCTION: Regex = Regex::new(r"\b(polylog|poly)\b").unwrap();
+}
+
+/// LaTeX commands for Greek letters
+const GREEK: [(char, &str); 42] = [
+    ('α', r"\alpha"),
+    ('β', r"\beta"),
+    ('γ', r"\gamma"),
+    ('δ', r"\delta"),
+    ('ε', r"\varepsilon"),
+    ('ϵ', r"\epsilon"),
+    ('ζ', r"\zeta"),
+    ('η', r"\eta"),
+    ('θ', r"\theta"),
+    ('ϑ', r"\vartheta"),
+    ('ι', r"\iota"),
+    ('κ', r"\kappa"),
+    ('λ', r"\lambda"),
+    ('μ', r"\mu"),
+    ('ν', r"\nu"),
+    ('ξ', r"\xi"),
+    ('π', r"\pi"),
+    ('ϖ', r"\varpi"),
+    ('ρ', r"\rho"),
+    ('ϱ', r"\varrho"),
+    ('σ', r"\sigma"),
+    ('ς', r"\varsigma"),
+    ('τ', r"\tau"),
+    ('υ', r"\upsilon"),
+    ('φ', r"\varphi"),
+    ('ϕ', r"\phi"),
+    ('χ', r"\chi"),
+    ('ψ', r"\psi"),
+    ('ω', r"\omega"),
+    ('Γ', r"\Gamma"),
+    ('Δ', r"\Delta"),
+    ('Θ', r"\Theta"),
+    ('Λ', r"\Lambda"),
+    ('Ξ', r"\Xi"),
+    ('Π', r"\Pi"),
+    ('Σ', r"\Sigma"),
+    ('Υ', r"\Upsilon"),
+    ('Φ', r"\Phi"),
+    ('Ψ', r"\Psi"),
+    ('Ω', r"\Omega"),
+    ('Ο', "O"),
+    ('ο', "o"),
+];
+
+/// Delimits inline math inserted by [`fix`] until LaTeX escaping writes it as `$...$`
+pub const DELIM: char = '\u{E000}';
+
+/// The byte ranges of inline math `$...$`, including the dollar signs
+///
+/// Escaped dollar signs are ignored and a dollar sign without a partner is not math.
+pub fn ranges(string: &str) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut start = None;
+    let mut escaped = false;
+    for (idx, ch) in string.char_indices() {
+        match ch {
+            '\\' => {
+                escaped = !escaped;
+                continue;
+            }
+            '$' if !escaped => match start.take() {
+                Some(start) => ranges.push(start..idx + 1),
+                None => start = Some(idx),
+            },
+            _ => {}
+        }
+        escaped = false;
+    }
+    ranges
+}
+
+/// The byte ranges of inline math delimited by [`DELIM`], including the delimiters
+pub fn marked(string: &str) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut start = None;
+    for (idx, _) in string.match_indices(DELIM) {
+        match start.take() {
+            Some(start) => ranges.push(start..idx + DELIM.len_utf8()),
+            None => start = Some(idx),
+        }
+    }
+    ranges
+}
+
+/// Writes inline math delimited by [`DELIM`] as `$...$`
+pub fn unmark(string: &mut String) {
+    if string.contains(DELIM) {
+        *string = string.replace(DELIM, "$");
+    }
+}
+
+/// Whether the byte position is inside inline math
+pub fn in_math(ranges: &[Range<usize>], pos: usize) -> bool {
+    ranges.iter().any(|range| range.contains(&pos))
+}
+
+/// Converts a script argument to a braced TeX group
+fn script(arg: &str) -> String {
+    let arg = arg
+        .strip_prefix('(')
+        .and_then(|arg| arg.strip_suffix(')'))
+        .or_else(|| arg.strip_prefix('{').and_then(|arg| arg.strip_suffix('}')))
+        .unwrap_or(arg);
+    if arg.chars().count() == 1 {
+        String::from(arg)
+    } else {
+        format!("{{{arg}}}")
+    }
+}
+
+/// Converts plain text math to TeX
+fn tex(math: &str) -> String {
+    let math = FUNCTION.replace_all(math, r"\$1");
+    let math = TEXT_FUNCTION.replace_all(&math, r"\mathrm{$1}");
+    let mut out = String::with_capacity(math.len());
+    let mut chars = math.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match GREEK.iter().find(|(greek, _)| *greek == ch) {
+            Some((_, cmd)) => {
+                out.push_str(cmd);
+                if chars.peek().is_some_and(|ch| ch.is_alphanumeric()) {
+                    out.push(' ');
+                }
+            }
+            None => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Wraps inline math in the string in [`DELIM`]
+pub fn fix(string: &mut String) {
+    let existing = marked(string);
+    // (range, replacement) of detected math, without overlaps
+    let mut found: Vec<(Range<usize>, String)> = vec![];
+    let mut add = |range: Range<usize>, replacement: String| {
+        if in_math(&existing, range.start)
+            || found
+                .iter()
+                .any(|(other, _)| other.start < range.end && range.start < other.end)
+        {
+            return;
+        }
+        found.push((range, replacement));
+    };
+    let whole = |caps: &Captures| caps.get(0).unwrap().range();
+
+    for caps in ASYMPTOTIC.captures_iter(string) {
+        add(whole(&caps), tex(&caps[0]));
+    }
+    for caps in HIERARCHY.captures_iter(string) {
+        let sub = caps.get(2).or(caps.get(4)).map(|sub| sub.as_str());
+        let mut math = tex(&caps[1]);
+        if let Some(sub) = sub {
+            math.push('_');
+            math.push_str(&script(sub));
+        }
+        math.push('^');
+        math.push_str(&caps[3]);
+        add(whole(&caps), math);
+    }
+    for caps in POWER.captures_iter(string) {
+        add(
+            whole(&caps),
+            format!("{}^{}", tex(&caps[1]), script(&tex(&caps[2]))),
+        );
+    }
+    for caps in SUBSCRIPT.captures_iter(string) {
+        add(
+            whole(&caps),
+            format!("{}_{}", tex(&caps[1]), script(&tex(&caps[2]))),
+        );
+    }
+    for matched in GREEK_LETTER.find_iter(string) {
+        add(matched.range(), tex(matched.as_str()));
+    }
+    for caps in VARIABLE_PREFIX
+        .captures_iter(string)
+        .chain(VARIABLE_SUFFIX.captures_iter(string))
+    {
+        let var = caps.get(1).unwrap();
+        add(var.range(), String::from(var.as_str()));
+    }
+
+    if found.is_empty() {
+        return;
+    }
+    found.sort_unstable_by_key(|(range, _)| range.start);
+    let mut out = String::with_capacity(string.len() + 2 * DELIM.len_utf8() * found.len());
+    let mut last = 0;
+    for (range, math) in found {
+        out.push_str(&string[last..range.start]);
+        out.push(DELIM);
+        out.push_str(&math);
+        out.push(DELIM);
+        last = range.end;
+    }
+    out.push_str(&string[last..]);
+    *string = out;
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn dblp_titles() {
+        // titles from DBLP, complemented by variations covering the remaining patterns
+        let cases = [
+            ("On the Complexity of k-SAT", "On the Complexity of $k$-SAT"),
+            (
+                "Results on the Propositional μ-Calculus",
+                r"Results on the Propositional $\mu$-Calculus",
+            ),
+            (
+                "The Polyadic π-Calculus: a Tutorial",
+                r"The Polyadic $\pi$-Calculus: a Tutorial",
+            ),
+            (
+                "An O(n log n) Algorithm for Maximum st-Flow in a Directed Planar Graph",
+                r"An $O(n \log n)$ Algorithm for Maximum st-Flow in a Directed Planar Graph",
+            ),
+            (
+                "An n^5/2 Algorithm for Maximum Matchings in Bipartite Graphs",
+                r"An $n^{5/2}$ Algorithm for Maximum Matchings in Bipartite Graphs",
+            ),
+            (
+                "An n^(5/2) Algorithm for Maximum Matchings in Bipartite Graphs",
+                r"An $n^{5/2}$ Algorithm for Maximum Matchings in Bipartite Graphs",
+            ),
+            (
+                "Efficient Top-k Query Processing",
+                r"Efficient Top-$k$ Query Processing",
+            ),
+            (
+                "2^n Lower Bounds for Resolution",
+                r"$2^n$ Lower Bounds for Resolution",
+            ),
+            (
+                "Deciding Σ2P-Complete Problems with QBF Solvers",
+                r"Deciding $\Sigma_2^P$-Complete Problems with QBF Solvers",
+            ),
+            (
+                "The Complexity of Π^p_2 Problems",
+                r"The Complexity of $\Pi_2^p$ Problems",
+            ),
+            (
+                "Approximating x_1 and x_{max} in Θ(log^2 n) Time",
+                r"Approximating $x_1$ and $x_{\max}$ in $\Theta(\log^2 n)$ Time",
+            ),
+            (
+                "An α-β Pruning Algorithm for Poly(n) Games",
+                r"An $\alpha$-$\beta$ Pruning Algorithm for Poly(n) Games",
+            ),
+            // not math
+            ("SAT-Based MaxSAT Algorithms", "SAT-Based MaxSAT Algorithms"),
+            ("e-Commerce for Everyone", "e-Commerce for Everyone"),
+            (
+                "m-Commerce Adoption in Europe",
+                "m-Commerce Adoption in Europe",
+            ),
+            (
+                "Testing d-Separation in Bayesian Networks",
+                "Testing d-Separation in Bayesian Networks",
+            ),
+            (
+                "Visualizing Data using t-SNE",
+                "Visualizing Data using t-SNE",
+            ),
+            (
+                "Compiling Propositional Formulas into d-DNNF",
+                "Compiling Propositional Formulas into d-DNNF",
+            ),
+            (
+                "From $5 to $10 per k-SAT Call",
+                "From $5 to $10 per $k$-SAT Call",
+            ),
+            ("Already \u{E000}k\u{E000}-SAT", "Already $k$-SAT"),
+        ];
+        for (input, expected) in cases {
+            let mut text = String::from(input);
+            super::fix(&mut text);
+            super::unmark(&mut text);
+            assert_eq!(text, expected, "input: {input}");
+        }
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(super::ranges("a $b$ c").first(), Some(&(2..5)));
+        assert!(super::ranges(r"a \$b$ c").is_empty());
+        assert_eq!(super::ranges("a $b$ $c$"), vec![2..5, 6..9]);
+        assert_eq!(super::ranges("a $b$ $c").len(), 1);
+        assert_eq!(super::marked("a \u{E000}b\u{E000} $c$"), vec![2..9]);
+    }
+}
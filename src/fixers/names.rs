@@ -1,61 +1,282 @@
-/// Converts a name of form `<first> <middle> <last>` into `<last>, <first> <middle` while catching
-/// some common last names that have more than one word
-pub fn fix(name: &mut String) {
-    let tokens: Vec<_> = name.split_whitespace().map(String::from).collect();
-    name.clear();
-
-    // Case 1: if any but the first token starts with lower case, likely `van ...`, `de la ...`
-    for (idx, token) in tokens.iter().enumerate().skip(1) {
-        if let Some(first) = token.chars().next()
-            && first.is_lowercase()
-        {
-            convert(name, &tokens, idx);
-            return;
+//! # Personal Names
+//!
+//! Splits names as DBLP writes them, `<given> <particle> <family> <suffix>`, into their parts and
+//! writes them in the BibTeX form `<particle> <family>, <suffix>, <given>`. Names that the
+//! heuristics get wrong, e.g., people with two family names, can be listed in a dictionary.
+
+/// Suffixes that follow the family name
+const SUFFIXES: [&str; 9] = ["Jr.", "Jr", "Sr.", "Sr", "II", "III", "IV", "2nd", "3rd"];
+
+/// Capitalized words that start a family name, e.g., in `Daniel Le Berre`
+const FAMILY_STARTS: [&str; 12] = [
+    "Dal", "De", "Del", "Della", "Di", "Du", "La", "Le", "Ten", "Ter", "Van", "Von",
+];
+
+/// Words joining two family names, e.g., in `José Ortega y Gasset`
+const CONNECTORS: [&str; 2] = ["y", "i"];
+
+/// The parts of a personal name
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Name {
+    #[serde(default)]
+    pub given: String,
+    /// Lower case particles preceding the family name, e.g., `van der`
+    #[serde(default)]
+    pub particle: String,
+    pub family: String,
+    /// E.g., `Jr.` or `III`
+    #[serde(default)]
+    pub suffix: String,
+}
+
+/// Splits the name at whitespace outside of braces
+fn tokens(name: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut depth = 0usize;
+    let mut start = None;
+    for (idx, ch) in name.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ch if ch.is_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    tokens.push(&name[start..idx]);
+                }
+                continue;
+            }
+            _ => {}
         }
+        start.get_or_insert(idx);
     }
+    if let Some(start) = start {
+        tokens.push(&name[start..]);
+    }
+    tokens
+}
 
-    // Case 2: if any but the first token is a special word, treat it as the last name start
-    for (idx, token) in tokens.iter().enumerate().skip(1) {
-        if matches!(token.as_ref(), "Le" | "La" | "Van" | "Von") {
-            convert(name, &tokens, idx);
-            return;
+fn starts_lowercase(token: &str) -> bool {
+    token.chars().next().is_some_and(char::is_lowercase)
+}
+
+/// Whether the token is a particle, i.e., lower case only, e.g., `van` but not `d'Alembert`
+fn is_particle(token: &str) -> bool {
+    starts_lowercase(token) && !token.chars().any(char::is_uppercase)
+}
+
+impl Name {
+    /// Splits a name in DBLP form into its parts
+    ///
+    /// Returns `None` for empty names.
+    pub fn parse(name: &str) -> Option<Self> {
+        let mut tokens = tokens(name);
+        let suffix = if tokens.len() > 1 && SUFFIXES.contains(tokens.last()?) {
+            tokens.pop()
+        } else {
+            None
+        };
+        let last = tokens.len().checked_sub(1)?;
+        let join = |tokens: &[&str]| tokens.join(" ");
+
+        // the family name starts with the first particle, the word before a connector, a
+        // capitalized particle, or otherwise is the last word
+        let start = tokens
+            .iter()
+            .enumerate()
+            .skip(1)
+            .find_map(|(idx, &token)| {
+                if CONNECTORS.contains(&token) && idx > 1 && idx < last {
+                    Some(idx - 1)
+                } else if starts_lowercase(token) || (FAMILY_STARTS.contains(&token) && idx < last)
+                {
+                    Some(idx)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(last);
+        let mut family_start = start;
+        if !CONNECTORS.contains(&tokens.get(start + 1).copied().unwrap_or_default()) {
+            while family_start < last && is_particle(tokens[family_start]) {
+                family_start += 1;
+            }
         }
+
+        Some(Self {
+            given: join(&tokens[..start]),
+            particle: join(&tokens[start..family_start]),
+            family: join(&tokens[family_start..]),
+            suffix: suffix.map(String::from).unwrap_or_default(),
+        })
     }
 
-    convert(name, &tokens, tokens.len() - 1);
+    /// Splits a name in BibTeX form, `<particle> <family>, <suffix>, <given>`, into its parts
+    ///
+    /// Names without a comma are parsed as in DBLP form. Returns `None` for empty names.
+    pub fn parse_bibtex(name: &str) -> Option<Self> {
+        let parts: Vec<_> = name.split(',').map(str::trim).collect();
+        let (family, suffix, given) = match parts[..] {
+            [family, given] => (family, "", given),
+            [family, suffix, given] => (family, suffix, given),
+            _ => return Self::parse(name),
+        };
+        let tokens = tokens(family);
+        let family_start = tokens
+            .iter()
+            .take(tokens.len().saturating_sub(1))
+            .take_while(|token| is_particle(token))
+            .count();
+        let mut family = tokens[family_start..].join(" ");
+        // strip the braces protecting inner lower case words
+        if tokens.len() == family_start + 1
+            && let Some(inner) = family.strip_prefix('{').and_then(|f| f.strip_suffix('}'))
+        {
+            family = String::from(inner);
+        }
+        Some(Self {
+            given: String::from(given),
+            particle: tokens[..family_start].join(" "),
+            family,
+            suffix: String::from(suffix),
+        })
+    }
 }
 
-fn convert(out: &mut String, tokens: &[String], last_name_start: usize) {
-    debug_assert!(out.is_empty());
-    for (idx, token) in tokens[last_name_start..].iter().enumerate() {
-        out.push_str(token);
-        if idx + last_name_start + 1 < tokens.len() {
-            out.push(' ');
+impl std::fmt::Display for Name {
+    /// Writes the name in the form `<particle> <family>, <suffix>, <given>`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.particle.is_empty() {
+            write!(f, "{} ", self.particle)?;
         }
-    }
-    out.push_str(", ");
-    for (idx, token) in tokens[..last_name_start].iter().enumerate() {
-        out.push_str(token);
-        if idx + 1 < last_name_start {
-            out.push(' ');
+        // inner lower case words of the family name would otherwise be read as particles
+        let family = tokens(&self.family);
+        if family[..family.len().saturating_sub(1)]
+            .iter()
+            .any(|token| starts_lowercase(token))
+        {
+            write!(f, "{{{}}}", self.family)?;
+        } else {
+            write!(f, "{}", self.family)?;
+        }
+        if !self.suffix.is_empty() {
+            write!(f, ", {}", self.suffix)?;
         }
+        if !self.given.is_empty() || !self.suffix.is_empty() {
+            write!(f, ", {}", self.given)?;
+        }
+        Ok(())
     }
 }
 
+/// Converts a name of form `<given> <family>` into `<family>, <given>`
+///
+/// Names listed in the dictionary, by DBLP person identifier or name, are used as is. Names that
+/// already contain a comma are assumed to be in BibTeX form and, like empty names, left unchanged.
+pub fn fix(name: &mut String, pid: Option<&str>, dict: &super::Dictionary) {
+    if name.contains(',') {
+        return;
+    }
+    let parsed = match pid
+        .and_then(|pid| dict.name(pid))
+        .or_else(|| dict.name(name.trim()))
+    {
+        Some(parsed) => parsed.clone(),
+        None => match Name::parse(name) {
+            Some(parsed) => parsed,
+            None => return,
+        },
+    };
+    *name = parsed.to_string();
+}
+
 #[cfg(test)]
 mod tests {
+    use super::super::Dictionary;
+
+    fn fixed(name: &str, dict: &Dictionary) -> String {
+        let mut name = String::from(name);
+        super::fix(&mut name, None, dict);
+        name
+    }
+
     #[test]
     fn examples() {
-        let mut name = String::from("Christoph Jabs");
-        super::fix(&mut name);
-        assert_eq!(name, "Jabs, Christoph");
+        let dict = Dictionary::default();
+        let cases = [
+            ("Christoph Jabs", "Jabs, Christoph"),
+            ("Daniel Le Berre", "Le Berre, Daniel"),
+            ("Maria Garcia de la Banda", "de la Banda, Maria Garcia"),
+            ("Luc De Raedt", "De Raedt, Luc"),
+            ("Wil M. P. van der Aalst", "van der Aalst, Wil M. P."),
+            ("Guy L. Steele Jr.", "Steele, Jr., Guy L."),
+            ("Ludwig van Beethoven III", "van Beethoven, III, Ludwig"),
+            ("José Ortega y Gasset", "{Ortega y Gasset}, José"),
+            ("Jean d'Alembert", "d'Alembert, Jean"),
+            (
+                "Muhammad ibn Musa al-Khwarizmi",
+                "ibn Musa al-Khwarizmi, Muhammad",
+            ),
+            ("Jae-Hyun Kim", "Kim, Jae-Hyun"),
+            ("Prabhakar", "Prabhakar"),
+            ("Jean {de la Fontaine}", "{de la Fontaine}, Jean"),
+            // unchanged
+            ("Jabs, Christoph", "Jabs, Christoph"),
+            ("", ""),
+            ("  ", "  "),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(fixed(input, &dict), expected, "input: {input:?}");
+        }
+    }
+
+    #[test]
+    fn overrides() {
+        let dict = Dictionary::parse(
+            r#"
+[names."Juan Carlos Martínez García"]
+given = "Juan Carlos"
+family = "Martínez García"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            fixed("Juan Carlos Martínez García", &dict),
+            "Martínez García, Juan Carlos"
+        );
+        assert_eq!(
+            fixed("Juan Carlos Martínez", &dict),
+            "Martínez, Juan Carlos"
+        );
 
-        let mut name = String::from("Daniel Le Berre");
-        super::fix(&mut name);
-        assert_eq!(name, "Le Berre, Daniel");
+        let dict = Dictionary::parse(
+            r#"
+[names."12/3456"]
+given = "Juan Carlos"
+family = "Martínez García"
+"#,
+        )
+        .unwrap();
+        let mut name = String::from("Juan Carlos Martínez García");
+        super::fix(&mut name, Some("12/3456"), &dict);
+        assert_eq!(name, "Martínez García, Juan Carlos");
+    }
 
-        let mut name = String::from("Maria Garcia de la Banda");
-        super::fix(&mut name);
-        assert_eq!(name, "de la Banda, Maria Garcia");
+    #[test]
+    fn bibtex_form() {
+        let dict = Dictionary::default();
+        for input in [
+            "Wil M. P. van der Aalst",
+            "Guy L. Steele Jr.",
+            "José Ortega y Gasset",
+            "Christoph Jabs",
+        ] {
+            let fixed = fixed(input, &dict);
+            assert_eq!(
+                super::Name::parse_bibtex(&fixed),
+                super::Name::parse(input),
+                "input: {input:?}"
+            );
+        }
+        assert_eq!(super::Name::parse_bibtex(""), None);
     }
 }
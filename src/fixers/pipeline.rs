@@ -0,0 +1,580 @@
+//! # Fixer Pipeline
+//!
+//! The registry of fixers applied to records fetched from DBLP. Each fixer has a name under which
+//! it can be enabled, disabled and reordered, and declares which fixers it must run after.
+
+use std::{fmt, sync::Arc};
+
+use super::{casing::Case, corrections::Corrections, dictionary::Dictionary};
+use crate::dblp::Record;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Unknown fixer `{0}`, see `dblp fixers list`")]
+    UnknownFixer(String),
+    #[error("Fixer `{0}` is listed more than once in the fixer order")]
+    DuplicateFixer(String),
+    #[error("Fixer `{0}` must run after `{1}`")]
+    Order(&'static str, &'static str),
+}
+
+/// A message from a fixer about a record
+#[derive(Clone, Debug)]
+pub struct Note {
+    pub fixer: &'static str,
+    pub message: String,
+}
+
+/// A field changed by a fixer
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct Change {
+    pub fixer: &'static str,
+    pub field: &'static str,
+    /// The value before the fixer, `None` if the field was added
+    pub before: Option<String>,
+    /// The value after the fixer, `None` if the field was removed
+    pub after: Option<String>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} ", self.fixer, self.field)?;
+        match (&self.before, &self.after) {
+            (Some(before), Some(after)) => write!(f, "\"{before}\" -> \"{after}\""),
+            (None, Some(after)) => write!(f, "added \"{after}\""),
+            (Some(before), None) => write!(f, "removed \"{before}\""),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// What fixers reported while being applied to a record
+#[derive(Clone, Debug, Default)]
+pub struct FixReport {
+    current: &'static str,
+    notes: Vec<Note>,
+    /// Whether to record the changes of each fixer
+    explain: bool,
+    changes: Vec<Change>,
+}
+
+/// The fields of a record, with multiple values of the same field joined
+fn snapshot(rec: &Record) -> Vec<(&'static str, String)> {
+    let mut fields: Vec<(&'static str, String)> = vec![];
+    for (name, mut value) in rec.fields() {
+        super::math::unmark(&mut value);
+        match fields.iter_mut().find(|(known, _)| *known == name) {
+            Some((_, joined)) => {
+                joined.push_str("; ");
+                joined.push_str(&value);
+            }
+            None => fields.push((name, value)),
+        }
+    }
+    fields
+}
+
+impl FixReport {
+    /// A report that records the changes of each fixer
+    pub fn explaining() -> Self {
+        Self {
+            explain: true,
+            ..Self::default()
+        }
+    }
+
+    /// Adds a note from the currently applied fixer
+    pub fn note<S: Into<String>>(&mut self, message: S) -> &mut Self {
+        self.notes.push(Note {
+            fixer: self.current,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn notes(&self) -> &[Note] {
+        &self.notes
+    }
+
+    /// The changed fields, if recording changes
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    fn record_changes(
+        &mut self,
+        before: Vec<(&'static str, String)>,
+        after: Vec<(&'static str, String)>,
+    ) {
+        for (field, value) in &after {
+            let old = before.iter().find(|(name, _)| name == field);
+            if old.is_none_or(|(_, old)| old != value) {
+                self.changes.push(Change {
+                    fixer: self.current,
+                    field,
+                    before: old.map(|(_, old)| old.clone()),
+                    after: Some(value.clone()),
+                });
+            }
+        }
+        for (field, value) in before {
+            if !after.iter().any(|(name, _)| *name == field) {
+                self.changes.push(Change {
+                    fixer: self.current,
+                    field,
+                    before: Some(value),
+                    after: None,
+                });
+            }
+        }
+    }
+}
+
+pub trait Fixer: Send + Sync {
+    /// The name to refer to the fixer by
+    fn name(&self) -> &'static str;
+
+    /// A short description of what the fixer does
+    fn description(&self) -> &'static str;
+
+    /// The fixers that must run before this one, if they are enabled
+    fn after(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Whether the fixer is enabled unless disabled explicitly
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn apply(&self, rec: &mut Record, report: &mut FixReport);
+}
+
+/// A fixer implemented by a plain function
+struct Simple {
+    name: &'static str,
+    description: &'static str,
+    after: &'static [&'static str],
+    fix: fn(&mut Record),
+}
+
+impl Fixer for Simple {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn after(&self) -> &'static [&'static str] {
+        self.after
+    }
+
+    fn apply(&self, rec: &mut Record, _: &mut FixReport) {
+        (self.fix)(rec)
+    }
+}
+
+struct Names(Arc<Dictionary>);
+
+impl Fixer for Names {
+    fn name(&self) -> &'static str {
+        "names"
+    }
+
+    fn description(&self) -> &'static str {
+        "Writes names as `Last, First`, using the name overrides from the dictionaries"
+    }
+
+    fn after(&self) -> &'static [&'static str] {
+        &["author_num"]
+    }
+
+    fn apply(&self, rec: &mut Record, _: &mut FixReport) {
+        super::names(rec, &self.0)
+    }
+}
+
+struct ProperNouns(Arc<Dictionary>);
+
+impl Fixer for ProperNouns {
+    fn name(&self) -> &'static str {
+        "proper_nouns"
+    }
+
+    fn description(&self) -> &'static str {
+        "Protects proper nouns from the dictionaries in titles"
+    }
+
+    fn after(&self) -> &'static [&'static str] {
+        &["escape_latex"]
+    }
+
+    fn apply(&self, rec: &mut Record, _: &mut FixReport) {
+        super::proper_nouns(rec, &self.0)
+    }
+}
+
+struct Acronyms(Arc<Dictionary>);
+
+impl Fixer for Acronyms {
+    fn name(&self) -> &'static str {
+        "acronyms"
+    }
+
+    fn description(&self) -> &'static str {
+        "Protects acronyms such as `SAT` or `MaxSAT` in titles"
+    }
+
+    fn after(&self) -> &'static [&'static str] {
+        &["escape_latex", "proper_nouns"]
+    }
+
+    fn apply(&self, rec: &mut Record, _: &mut FixReport) {
+        super::acronyms(rec, &self.0)
+    }
+}
+
+struct Casing(Case);
+
+impl Fixer for Casing {
+    fn name(&self) -> &'static str {
+        "casing"
+    }
+
+    fn description(&self) -> &'static str {
+        "Converts titles to Title Case or sentence case, keeping protected text"
+    }
+
+    fn after(&self) -> &'static [&'static str] {
+        &[
+            "escape_latex",
+            "capital_after_colon",
+            "proper_nouns",
+            "acronyms",
+        ]
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn apply(&self, rec: &mut Record, _: &mut FixReport) {
+        super::casing(rec, self.0)
+    }
+}
+
+struct ManualCorrections(Corrections);
+
+impl Fixer for ManualCorrections {
+    fn name(&self) -> &'static str {
+        "corrections"
+    }
+
+    fn description(&self) -> &'static str {
+        "Applies the built-in and user-supplied manual corrections"
+    }
+
+    fn after(&self) -> &'static [&'static str] {
+        &["escape_latex", "proper_nouns", "acronyms", "casing"]
+    }
+
+    fn apply(&self, rec: &mut Record, report: &mut FixReport) {
+        self.0.apply(rec, report)
+    }
+}
+
+/// An ordered list of fixers that can be enabled and disabled
+#[derive(Clone, Default)]
+pub struct Pipeline {
+    fixers: Vec<(Arc<dyn Fixer>, bool)>,
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.fixers.iter().map(|(fixer, on)| (fixer.name(), on)))
+            .finish()
+    }
+}
+
+impl Pipeline {
+    /// All built-in fixers in their default order
+    pub fn builtin(dictionary: Dictionary, corrections: Corrections, case: Case) -> Self {
+        let dictionary = Arc::new(dictionary);
+        let simple = |name, description, after, fix| -> Arc<dyn Fixer> {
+            Arc::new(Simple {
+                name,
+                description,
+                after,
+                fix,
+            })
+        };
+        let fixers: Vec<Arc<dyn Fixer>> = vec![
+            simple(
+                "author_num",
+                "Strips DBLP disambiguation numbers from author and editor names",
+                &[],
+                super::author_num,
+            ),
+            simple(
+                "math",
+                "Wraps inline math such as `O(n log n)` in titles in `$...$`",
+                &[],
+                super::math,
+            ),
+            simple(
+                "escape_latex",
+                "Escapes characters with special meaning in LaTeX, except in inline math",
+                &["math"],
+                super::escape_latex,
+            ),
+            simple(
+                "page_range",
+                "Uses `--` in page ranges",
+                &[],
+                super::page_range,
+            ),
+            Arc::new(Names(dictionary.clone())),
+            simple(
+                "strip_title_period",
+                "Strips trailing periods from titles",
+                &[],
+                super::strip_title_period,
+            ),
+            simple(
+                "capital_after_colon",
+                "Protects capital letters after a colon in titles",
+                &["escape_latex"],
+                super::capital_after_colon,
+            ),
+            Arc::new(ProperNouns(dictionary.clone())),
+            Arc::new(Acronyms(dictionary)),
+            Arc::new(Casing(case)),
+            simple(
+                "weird_urls",
+                "Removes Wikidata and AAAI OJS links",
+                &[],
+                super::weird_urls,
+            ),
+            simple(
+                "date_ranges",
+                "Uses en-dashes in date ranges in venue titles",
+                &["escape_latex"],
+                super::date_ranges,
+            ),
+            simple(
+                "dashes",
+                "Uses em-dashes for ` - ` in titles, venues and publishers",
+                &["escape_latex"],
+                super::dashes,
+            ),
+            Arc::new(ManualCorrections(corrections)),
+            simple(
+                "unicode",
+                "Converts unicode characters to LaTeX",
+                &["escape_latex", "corrections"],
+                super::unicode,
+            ),
+            simple(
+                "single_external",
+                "Keeps only one external link, preferring DOIs",
+                &["weird_urls"],
+                super::single_external,
+            ),
+        ];
+        Self {
+            fixers: fixers
+                .into_iter()
+                .map(|fixer| {
+                    let enabled = fixer.enabled_by_default();
+                    (fixer, enabled)
+                })
+                .collect(),
+        }
+    }
+
+    fn position(&self, name: &str) -> Result<usize, Error> {
+        self.fixers
+            .iter()
+            .position(|(fixer, _)| fixer.name() == name)
+            .ok_or_else(|| Error::UnknownFixer(String::from(name)))
+    }
+
+    pub fn enable(&mut self, name: &str) -> Result<&mut Self, Error> {
+        let idx = self.position(name)?;
+        self.fixers[idx].1 = true;
+        Ok(self)
+    }
+
+    pub fn disable(&mut self, name: &str) -> Result<&mut Self, Error> {
+        let idx = self.position(name)?;
+        self.fixers[idx].1 = false;
+        Ok(self)
+    }
+
+    /// Reorders the given fixers among the positions they take up, leaving all other fixers in
+    /// place
+    pub fn reorder<S: AsRef<str>>(&mut self, order: &[S]) -> Result<&mut Self, Error> {
+        let mut positions = Vec::with_capacity(order.len());
+        for name in order {
+            let idx = self.position(name.as_ref())?;
+            if positions.contains(&idx) {
+                return Err(Error::DuplicateFixer(String::from(name.as_ref())));
+            }
+            positions.push(idx);
+        }
+        let fixers: Vec<_> = positions
+            .iter()
+            .map(|&idx| self.fixers[idx].clone())
+            .collect();
+        positions.sort_unstable();
+        for (idx, fixer) in positions.into_iter().zip(fixers) {
+            self.fixers[idx] = fixer;
+        }
+        Ok(self)
+    }
+
+    /// Checks that the enabled fixers satisfy their ordering constraints
+    pub fn check(&self) -> Result<(), Error> {
+        for (idx, (fixer, enabled)) in self.fixers.iter().enumerate() {
+            if !enabled {
+                continue;
+            }
+            for &after in fixer.after() {
+                if let Some((later, true)) = self.fixers[idx + 1..]
+                    .iter()
+                    .find(|(other, _)| other.name() == after)
+                {
+                    return Err(Error::Order(fixer.name(), later.name()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Iterates over all fixers in order, together with whether they are enabled
+    pub fn fixers(&self) -> impl Iterator<Item = (&dyn Fixer, bool)> {
+        self.fixers
+            .iter()
+            .map(|(fixer, enabled)| (fixer.as_ref(), *enabled))
+    }
+
+    /// Applies all enabled fixers to the record
+    pub fn apply(&self, rec: &mut Record, report: &mut FixReport) {
+        for (fixer, enabled) in &self.fixers {
+            if !enabled {
+                continue;
+            }
+            report.current = fixer.name();
+            if report.explain {
+                let before = snapshot(rec);
+                fixer.apply(rec, report);
+                report.record_changes(before, snapshot(rec));
+            } else {
+                fixer.apply(rec, report);
+            }
+        }
+        super::unmark_math(rec);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, FixReport, Pipeline};
+    use crate::dblp::{
+        Record,
+        record::{Crossref, Person},
+    };
+    use crate::fixers::{corrections::Corrections, dictionary::Dictionary};
+
+    #[test]
+    fn explain() {
+        let mut rec = Record::Inproceedings {
+            key: String::from("conf/sat/Example24"),
+            author: vec![Person::from("Jane Doe 0001")],
+            title: String::from("SAT-Based Solving."),
+            booktitle: String::from("SAT"),
+            year: 2024,
+            pages: Some(String::from("1-10")),
+            external: vec![],
+            crossref: Crossref::Key(String::from("conf/sat/2024")),
+            usera: None,
+        };
+        let mut report = FixReport::explaining();
+        builtin().apply(&mut rec, &mut report);
+        let changes: Vec<_> = report
+            .changes()
+            .iter()
+            .map(|change| change.to_string())
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                r#"author_num: author "Jane Doe 0001" -> "Jane Doe""#,
+                r#"page_range: pages "1-10" -> "1--10""#,
+                r#"names: author "Jane Doe" -> "Doe, Jane""#,
+                r#"strip_title_period: title "SAT-Based Solving." -> "SAT-Based Solving""#,
+                r#"acronyms: title "SAT-Based Solving" -> "{SAT}-Based Solving""#,
+                r#"acronyms: booktitle "SAT" -> "{SAT}""#,
+            ]
+        );
+
+        let mut report = FixReport::default();
+        builtin().apply(&mut rec, &mut report);
+        assert!(report.changes().is_empty());
+    }
+
+    fn builtin() -> Pipeline {
+        Pipeline::builtin(
+            Dictionary::builtin(),
+            Corrections::builtin(),
+            Default::default(),
+        )
+    }
+
+    fn names(pipeline: &Pipeline) -> Vec<&'static str> {
+        pipeline.fixers().map(|(fixer, _)| fixer.name()).collect()
+    }
+
+    #[test]
+    fn builtin_order() {
+        let pipeline = builtin();
+        pipeline.check().unwrap();
+        let names = names(&pipeline);
+        for (fixer, _) in pipeline.fixers() {
+            for after in fixer.after() {
+                assert!(names.contains(after), "unknown fixer `{after}`");
+            }
+        }
+    }
+
+    #[test]
+    fn reorder() {
+        let mut pipeline = builtin();
+        pipeline.reorder(&["dashes", "date_ranges"]).unwrap();
+        let names = names(&pipeline);
+        let dashes = names.iter().position(|&n| n == "dashes").unwrap();
+        assert_eq!(names[dashes + 1], "date_ranges");
+        pipeline.check().unwrap();
+
+        pipeline.reorder(&["acronyms", "proper_nouns"]).unwrap();
+        assert!(matches!(
+            pipeline.check(),
+            Err(Error::Order("acronyms", "proper_nouns"))
+        ));
+        // constraints on disabled fixers are ignored
+        pipeline.disable("proper_nouns").unwrap();
+        pipeline.check().unwrap();
+
+        assert!(matches!(
+            pipeline.reorder(&["dashes", "dashes"]),
+            Err(Error::DuplicateFixer(_))
+        ));
+        assert!(matches!(
+            pipeline.enable("nonexistent"),
+            Err(Error::UnknownFixer(_))
+        ));
+    }
+}
@@ -1540,11 +1540,176 @@ lazy_static! {
         ('\u{1D7FE}', r"\ensuremath{\mathtt{8}}"),                           // MATHEMATICAL MONOSPACE DIGIT EIGHT [𝟾]
         ('\u{1D7FF}', r"\ensuremath{\mathtt{9}}"),                           // MATHEMATICAL MONOSPACE DIGIT NINE [𝟿]
     ]);
+    /// The inverse of [`UNICODE_2_LATEX`], keyed by normalized commands
+    static ref LATEX_2_UNICODE: HashMap<String, char> = {
+        let mut map: HashMap<String, char> = HashMap::new();
+        for (&ch, &latex) in UNICODE_2_LATEX.iter() {
+            if !latex.starts_with('\\') {
+                continue;
+            }
+            let key = match command(latex) {
+                Some((key, len)) if len == latex.len() => key,
+                _ => String::from(latex),
+            };
+            // several characters can map to the same command, pick one deterministically
+            map.entry(key)
+                .and_modify(|other| *other = (*other).min(ch))
+                .or_insert(ch);
+        }
+        map.entry(String::from(r"\j")).or_insert('ȷ');
+        map
+    };
+}
+
+/// Accents that take an argument and are written as a single symbol, e.g., `\"a`
+const SYMBOL_ACCENTS: &str = "\"'`^~=.";
+/// Accents that take an argument and are written as a word, e.g., `\c{c}`
+const WORD_ACCENTS: [&str; 9] = ["b", "c", "d", "H", "k", "r", "t", "u", "v"];
+
+/// Treats the dotless `\i` and `\j` as `i` and `j`
+fn dotless(arg: &str) -> &str {
+    match arg {
+        r"\i" => "i",
+        r"\j" => "j",
+        arg => arg,
+    }
+}
+
+/// Parses the argument of an accent, returning it without braces and its length in bytes
+fn accent_argument(input: &str) -> Option<(&str, usize)> {
+    if let Some(rest) = input.strip_prefix('{') {
+        let end = rest.find('}')?;
+        return Some((dotless(rest[..end].trim()), end + 2));
+    }
+    if let Some(rest) = input.strip_prefix('\\') {
+        let name = rest
+            .find(|ch: char| !ch.is_ascii_alphabetic())
+            .map_or(rest, |end| &rest[..end]);
+        if !matches!(name, "i" | "j") {
+            return None;
+        }
+        // like any command word, the dotless letters swallow a following space
+        let len = if rest[1..].starts_with(' ') { 3 } else { 2 };
+        return Some((dotless(&input[..2]), len));
+    }
+    let ch = input.chars().next()?;
+    (!ch.is_whitespace() && ch != '}').then(|| (&input[..ch.len_utf8()], ch.len_utf8()))
+}
+
+/// Parses a LaTeX command at the start of the input
+///
+/// Returns the normalized command, where accent arguments are always braced, e.g., `\"{a}` for
+/// `\"a`, and the length of the command in the input in bytes.
+fn command(input: &str) -> Option<(String, usize)> {
+    let rest = input.strip_prefix('\\')?;
+    let first = rest.chars().next()?;
+    if SYMBOL_ACCENTS.contains(first) {
+        let (arg, len) = accent_argument(&rest[1..])?;
+        return Some((format!("\\{first}{{{arg}}}"), 2 + len));
+    }
+    if !first.is_ascii_alphabetic() {
+        return None;
+    }
+    let name_len = rest
+        .find(|ch: char| !ch.is_ascii_alphabetic())
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    let after = &rest[name_len..];
+    if WORD_ACCENTS.contains(&name) {
+        let arg = if after.starts_with('{') {
+            accent_argument(after)
+        } else {
+            // the space ending the command name is not part of the argument
+            after
+                .strip_prefix(' ')
+                .and_then(accent_argument)
+                .map(|(arg, len)| (arg, len + 1))
+        };
+        if let Some((arg, len)) = arg {
+            return Some((format!("\\{name}{{{arg}}}"), 1 + name_len + len));
+        }
+    }
+    Some((format!("\\{name}"), 1 + name_len))
+}
+
+/// Looks up the LaTeX command at the start of the input, returning the character and the length
+/// of the command in bytes
+fn lookup_command(input: &str) -> Option<(char, usize)> {
+    let (key, len) = command(input)?;
+    let after = &input[len..];
+    // commands with a braced argument that is not an accent, e.g., `\ensuremath{\alpha}`
+    if after.starts_with('{')
+        && let Some(end) = after.find('}')
+        && let Some(&ch) = LATEX_2_UNICODE.get(&input[..len + end + 1])
+    {
+        return Some((ch, len + end + 1));
+    }
+    let &ch = LATEX_2_UNICODE.get(&key)?;
+    // a space terminating a command word is swallowed, e.g., `Stra\ss e`
+    let swallow = key.ends_with(|ch: char| ch.is_ascii_alphabetic()) && after.starts_with(' ');
+    Some((ch, len + usize::from(swallow)))
+}
+
+/// Converts LaTeX commands for special characters to unicode
+///
+/// Handles braced (`{\"a}`) and unbraced (`\"a`, `\"{a}`) forms, as well as accents on the
+/// dotless `\i` and `\j`. Unknown commands and all other text are left unchanged.
+pub fn from_latex(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut idx = 0;
+    while idx < input.len() {
+        let rest = &input[idx..];
+        if let Some(inner) = rest.strip_prefix('{')
+            && inner.starts_with('\\')
+            && let Some((ch, len)) = lookup_command(inner)
+            && inner[len..].trim_start().starts_with('}')
+        {
+            out.push(ch);
+            let inner = &inner[len..];
+            idx += 1 + len + (inner.len() - inner.trim_start().len()) + 1;
+            continue;
+        }
+        if rest.starts_with('\\')
+            && let Some((ch, len)) = lookup_command(rest)
+        {
+            out.push(ch);
+            idx += len;
+            continue;
+        }
+        let ch = rest.chars().next().unwrap();
+        out.push(ch);
+        idx += ch.len_utf8();
+    }
+    out
+}
+
+/// Converts LaTeX commands for special characters in the string to unicode, see [`from_latex`]
+pub fn to_unicode(input: &mut String) {
+    if input.contains('\\') {
+        *input = from_latex(input);
+    }
+}
+
+/// Converts LaTeX to plain text, e.g., `{\"a}` to `ä` and `\&` to `&`, and drops braces
+pub fn plain_text(input: &str) -> String {
+    let input = from_latex(input);
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.peek().is_some_and(|next| "#$%&_{}".contains(*next)) => {
+                out.extend(chars.next())
+            }
+            '{' | '}' => {}
+            ch => out.push(ch),
+        }
+    }
+    out
 }
 
 pub fn replace(input: &mut String) {
     let mut out: Option<String> = None;
-    for (idx, char) in input.chars().enumerate() {
+    for (idx, char) in input.char_indices() {
         if char.is_ascii() {
             if let Some(out) = out.as_mut() {
                 out.push(char);
@@ -1566,3 +1731,65 @@ pub fn replace(input: &mut String) {
         let _ = std::mem::replace(input, out);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn from_latex() {
+        let cases = [
+            (r#"J{\"{a}}rvisalo"#, "Järvisalo"),
+            (r#"J{\"a}rvisalo"#, "Järvisalo"),
+            (r#"J\"arvisalo"#, "Järvisalo"),
+            (r#"J\"{a}rvisalo"#, "Järvisalo"),
+            (
+                r#"Berg, Jeremias and J{\"{a}}rvisalo, Matti"#,
+                "Berg, Jeremias and Järvisalo, Matti",
+            ),
+            (r"Ca{\~n}as", "Cañas"),
+            (r"Fran\c{c}ois", "François"),
+            (r"Fran\c cois", "François"),
+            (r"Stra\ss e", "Straße"),
+            (r"{\ss}", "ß"),
+            (r"Cl{\'{\i}}maco", "Clímaco"),
+            (r"Cl\'\i maco", "Clímaco"),
+            (r"{\i}", "ı"),
+            (r"{\o}", "ø"),
+            (r"D{\v{z}}eroski", "Džeroski"),
+            (r"1998{\textendash}2000", "1998–2000"),
+            (r"{\ensuremath{\alpha}}", "α"),
+            // left unchanged
+            (
+                r"Solving {MaxSAT} \& {SAT} in $O(n \log n)$",
+                r"Solving {MaxSAT} \& {SAT} in $O(n \log n)$",
+            ),
+            (r"Jos{\'e}~Doe", "José~Doe"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(super::from_latex(input), expected, "input: {input}");
+        }
+    }
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(
+            super::plain_text(r#"Sch{\"o}ning \& {Smith}: {\textemdash} 100\%"#),
+            "Schöning & Smith: — 100%"
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        for input in [
+            "Järvisalo",
+            "Clímaco",
+            "Straße",
+            "Džeroski",
+            "Łukasz Kraków",
+            "Ångström",
+        ] {
+            let mut text = String::from(input);
+            super::replace(&mut text);
+            assert_eq!(super::from_latex(&text), input, "latex: {text}");
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! # Importing Bibliographies
+//!
+//! Reads the entries of an existing bibliography, e.g., exported from Zotero, so that they can be
+//! matched to DBLP. Only what is needed for matching is kept: the citation key, title, family
+//! names of the authors, year and DOI. BibTeX (`.bib`), CSL-JSON (`.json`) and RIS (`.ris`)
+//! files are supported.
+
+use biblatex::ChunksExt;
+
+use crate::{
+    dblp::record::External,
+    fixers::{names::Name, unicode::plain_text},
+};
+
+pub mod matching;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid BibTeX file: {0}")]
+    Bibtex(#[from] biblatex::ParseError),
+    #[error("Invalid CSL-JSON file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Unknown bibliography format `{0}`, expected `bib`, `json` or `ris`")]
+    UnknownFormat(String),
+}
+
+/// An entry of an imported bibliography
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Entry {
+    pub key: String,
+    pub title: String,
+    /// The family names of the authors
+    pub authors: Vec<String>,
+    pub year: Option<u32>,
+    pub doi: Option<String>,
+}
+
+/// The first four-digit number in a date, e.g., `2024` in `2024-05-28`
+fn year(date: &str) -> Option<u32> {
+    date.split(|ch: char| !ch.is_ascii_digit())
+        .find(|part| part.len() == 4)
+        .and_then(|year| year.parse().ok())
+}
+
+/// A DOI without resolver prefix
+fn doi(value: &str) -> Option<String> {
+    match External::from(String::from(value.trim())) {
+        External::Doi(doi) => Some(doi),
+        External::Url(_) => None,
+    }
+}
+
+/// Reads a bibliography, choosing the format by the file extension
+pub fn read(path: &camino::Utf8Path) -> Result<Vec<Entry>, Error> {
+    let content = std::fs::read_to_string(path)?;
+    match path.extension() {
+        Some("bib") => parse_bibtex(&content),
+        Some("json") => parse_csl(&content),
+        Some("ris") => Ok(parse_ris(&content)),
+        ext => Err(Error::UnknownFormat(String::from(ext.unwrap_or_default()))),
+    }
+}
+
+pub fn parse_bibtex(content: &str) -> Result<Vec<Entry>, Error> {
+    let mut entries = vec![];
+    for entry in biblatex::Bibliography::parse(content)? {
+        let field = |name| entry.get(name).map(|chunks| chunks.format_verbatim());
+        entries.push(Entry {
+            key: entry.key.clone(),
+            title: field("title")
+                .map(|title| plain_text(&title))
+                .unwrap_or_default(),
+            authors: entry
+                .author()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|person| plain_text(&person.name))
+                .collect(),
+            year: field("year")
+                .or_else(|| field("date"))
+                .and_then(|date| year(&date)),
+            doi: field("doi").and_then(|value| doi(&value)),
+        });
+    }
+    Ok(entries)
+}
+
+#[derive(serde::Deserialize)]
+struct CslItem {
+    id: serde_json::Value,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    author: Vec<CslName>,
+    issued: Option<CslDate>,
+    #[serde(rename = "DOI")]
+    doi: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct CslName {
+    family: Option<String>,
+    literal: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct CslDate {
+    #[serde(rename = "date-parts", default)]
+    date_parts: Vec<Vec<serde_json::Value>>,
+    raw: Option<String>,
+}
+
+pub fn parse_csl(content: &str) -> Result<Vec<Entry>, Error> {
+    let items: Vec<CslItem> = serde_json::from_str(content)?;
+    Ok(items
+        .into_iter()
+        .map(|item| Entry {
+            key: match item.id {
+                serde_json::Value::String(id) => id,
+                id => id.to_string(),
+            },
+            title: plain_text(&item.title),
+            authors: item
+                .author
+                .into_iter()
+                .filter_map(|name| {
+                    name.family.or_else(|| {
+                        let literal = Name::parse(&name.literal?)?;
+                        Some(literal.family)
+                    })
+                })
+                .collect(),
+            year: item.issued.and_then(|date| {
+                let first = date.date_parts.first().and_then(|parts| parts.first());
+                match first {
+                    Some(serde_json::Value::Number(year)) => {
+                        year.as_u64().and_then(|year| u32::try_from(year).ok())
+                    }
+                    Some(serde_json::Value::String(year)) => self::year(year),
+                    _ => date.raw.as_deref().and_then(self::year),
+                }
+            }),
+            doi: item.doi.as_deref().and_then(doi),
+        })
+        .collect())
+}
+
+/// Parses RIS records, entries without `ID` are numbered
+pub fn parse_ris(content: &str) -> Vec<Entry> {
+    let mut entries = vec![];
+    let mut entry: Option<Entry> = None;
+    for line in content.lines() {
+        let Some((tag, value)) = line.split_once("  -") else {
+            continue;
+        };
+        let value = value.trim();
+        if tag == "TY" {
+            entry = Some(Entry::default());
+            continue;
+        }
+        let Some(current) = entry.as_mut() else {
+            continue;
+        };
+        match tag {
+            "ID" => current.key = String::from(value),
+            "TI" | "T1" if current.title.is_empty() => current.title = String::from(value),
+            "AU" | "A1" => {
+                let family = value.split(',').next().unwrap_or_default().trim();
+                current.authors.push(String::from(family));
+            }
+            "PY" | "Y1" | "DA" if current.year.is_none() => current.year = year(value),
+            "DO" => current.doi = doi(value),
+            "ER" => {
+                let mut done = entry.take().expect("checked above");
+                if done.key.is_empty() {
+                    done.key = format!("ris{}", entries.len() + 1);
+                }
+                entries.push(done);
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Entry;
+
+    fn expected(key: &str) -> Entry {
+        Entry {
+            key: String::from(key),
+            title: String::from(
+                "From Single-Objective to Bi-Objective Maximum Satisfiability Solving",
+            ),
+            authors: vec![String::from("Jabs"), String::from("Järvisalo")],
+            year: Some(2024),
+            doi: Some(String::from("10.1613/jair.1.15333")),
+        }
+    }
+
+    #[test]
+    fn bibtex() {
+        let entries = super::parse_bibtex(
+            r#"@article{jabs2024,
+  author = {Jabs, Christoph and J{\"a}rvisalo, Matti},
+  title = {From Single-Objective to Bi-Objective {Maximum} {Satisfiability} Solving},
+  date = {2024-01},
+  doi = {https://doi.org/10.1613/jair.1.15333},
+}"#,
+        )
+        .unwrap();
+        assert_eq!(entries, [expected("jabs2024")]);
+    }
+
+    #[test]
+    fn csl() {
+        let entries = super::parse_csl(
+            r#"[{
+  "id": "jabs2024",
+  "type": "article-journal",
+  "title": "From Single-Objective to Bi-Objective Maximum Satisfiability Solving",
+  "author": [{ "family": "Jabs", "given": "Christoph" }, { "literal": "Matti Järvisalo" }],
+  "issued": { "date-parts": [[2024, 1]] },
+  "DOI": "10.1613/jair.1.15333"
+}]"#,
+        )
+        .unwrap();
+        assert_eq!(entries, [expected("jabs2024")]);
+    }
+
+    #[test]
+    fn ris() {
+        let entries = super::parse_ris(
+            "TY  - JOUR
+TI  - From Single-Objective to Bi-Objective Maximum Satisfiability Solving
+AU  - Jabs, Christoph
+AU  - Järvisalo, Matti
+PY  - 2024/01/01/
+DO  - 10.1613/jair.1.15333
+ER  -
+",
+        );
+        assert_eq!(entries, [expected("ris1")]);
+    }
+}
@@ -0,0 +1,190 @@
+//! # Matching Entries to DBLP
+//!
+//! Scores DBLP search hits as candidates for an imported entry. A matching DOI is decisive,
+//! otherwise the score combines the similarity of the normalized titles with the share of the
+//! entry's authors found among the candidate's authors, and is lowered if the years differ by more
+//! than one, e.g., between a preprint and its published version.
+
+use std::fmt;
+
+use super::Entry;
+use crate::dblp::search::Hit;
+
+/// Weight of the title similarity in the score, the author overlap makes up the rest
+const TITLE_WEIGHT: f64 = 0.7;
+
+/// Penalty for years that are more than one year apart
+const YEAR_PENALTY: f64 = 0.2;
+
+/// How certain a match is
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Confidence {
+    None,
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl Confidence {
+    fn from_score(score: f64) -> Self {
+        if score >= 0.9 {
+            Confidence::High
+        } else if score >= 0.75 {
+            Confidence::Medium
+        } else if score >= 0.5 {
+            Confidence::Low
+        } else {
+            Confidence::None
+        }
+    }
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Confidence::None => write!(f, "none"),
+            Confidence::Low => write!(f, "low"),
+            Confidence::Medium => write!(f, "medium"),
+            Confidence::High => write!(f, "high"),
+        }
+    }
+}
+
+/// The best DBLP candidate for an entry
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match {
+    /// The DBLP key without prefix
+    pub key: String,
+    /// Between 0 and 1
+    pub score: f64,
+    pub confidence: Confidence,
+    /// Whether the DOIs matched
+    pub by_doi: bool,
+}
+
+/// Lower case words of a text, ignoring punctuation
+pub fn words(text: &str) -> Vec<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Edit distance between two strings, in characters
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Similarity of two titles between 0 and 1, ignoring case and punctuation
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a = words(a).join(" ");
+    let b = words(b).join(" ");
+    let len = a.chars().count().max(b.chars().count());
+    if len == 0 {
+        return 0.;
+    }
+    1. - levenshtein(&a, &b) as f64 / len as f64
+}
+
+/// The share of family names found among the names of the candidate's authors
+///
+/// Entries without authors overlap fully, so that they are judged by title alone.
+pub fn author_overlap<'a>(families: &[String], names: impl IntoIterator<Item = &'a str>) -> f64 {
+    if families.is_empty() {
+        return 1.;
+    }
+    let names: Vec<Vec<String>> = names.into_iter().map(words).collect();
+    let found = families
+        .iter()
+        .filter(|family| {
+            let family = words(family);
+            !family.is_empty()
+                && names.iter().any(|name| {
+                    name.windows(family.len())
+                        .any(|window| window == family.as_slice())
+                })
+        })
+        .count();
+    found as f64 / families.len() as f64
+}
+
+/// Scores a DBLP search hit as candidate for an entry
+pub fn score(entry: &Entry, hit: &Hit) -> Match {
+    let by_doi = entry
+        .doi
+        .as_deref()
+        .zip(hit.doi())
+        .is_some_and(|(a, b)| a.eq_ignore_ascii_case(b));
+    let score = if by_doi {
+        1.
+    } else {
+        let mut score = TITLE_WEIGHT * title_similarity(&entry.title, hit.title())
+            + (1. - TITLE_WEIGHT) * author_overlap(&entry.authors, hit.authors());
+        if entry.year.is_some_and(|year| year.abs_diff(hit.year()) > 1) {
+            score -= YEAR_PENALTY;
+        }
+        score.max(0.)
+    };
+    Match {
+        key: String::from(hit.key()),
+        score,
+        confidence: Confidence::from_score(score),
+        by_doi,
+    }
+}
+
+/// The best scoring candidate
+pub fn best<'a>(entry: &Entry, hits: impl IntoIterator<Item = &'a Hit>) -> Option<Match> {
+    hits.into_iter()
+        .map(|hit| score(entry, hit))
+        .max_by(|a, b| a.score.total_cmp(&b.score))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Confidence;
+
+    #[test]
+    fn similarity() {
+        let title = "From Single-Objective to Bi-Objective Maximum Satisfiability Solving";
+        assert_eq!(super::title_similarity(title, &format!("{title}.")), 1.);
+        assert_eq!(super::title_similarity(title, &title.to_lowercase()), 1.);
+        assert!(
+            super::title_similarity(
+                title,
+                "From Single Objective to Bi-Objective MaxSAT Solving"
+            ) > 0.75
+        );
+        assert!(super::title_similarity(title, "Core Boosting in SAT-Based Optimization") < 0.5);
+
+        let families = [String::from("Jabs"), String::from("Järvisalo")];
+        let names = ["Christoph Jabs", "Jeremias Berg", "Matti Järvisalo"];
+        assert_eq!(super::author_overlap(&families, names), 1.);
+        assert_eq!(super::author_overlap(&families, ["Christoph Jabs"]), 0.5);
+        let families = [String::from("van der Aalst")];
+        assert_eq!(
+            super::author_overlap(&families, ["Wil M. P. van der Aalst"]),
+            1.
+        );
+
+        assert_eq!(Confidence::from_score(0.95), Confidence::High);
+        assert_eq!(Confidence::from_score(0.8), Confidence::Medium);
+        assert_eq!(Confidence::from_score(0.1), Confidence::None);
+    }
+}
@@ -1,9 +1,14 @@
+pub mod bcf;
+pub mod tex;
+
 use std::{fs, io};
 
 pub struct CiteKeyIter {
     aux_stack: Vec<AuxFileIter<io::BufReader<fs::File>>>,
     base_path: camino::Utf8PathBuf,
     follow_inputs: bool,
+    bib_files: Vec<camino::Utf8PathBuf>,
+    aux_files: Vec<camino::Utf8PathBuf>,
 }
 
 impl CiteKeyIter {
@@ -17,8 +22,31 @@ impl CiteKeyIter {
             aux_stack: vec![aux_iter],
             base_path,
             follow_inputs,
+            bib_files: vec![],
+            aux_files: vec![aux_file.to_path_buf()],
         })
     }
+
+    /// The aux files opened so far, including the main aux file
+    pub fn aux_files(&self) -> &[camino::Utf8PathBuf] {
+        &self.aux_files
+    }
+
+    /// The BibTeX files referenced by `\bibdata` in the aux files read so far
+    pub fn bib_files(&self) -> &[camino::Utf8PathBuf] {
+        &self.bib_files
+    }
+}
+
+/// Resolves a bibliography database as referenced in LaTeX relative to the document directory
+pub fn bib_path(base_path: &camino::Utf8Path, name: &str) -> camino::Utf8PathBuf {
+    let name = name.trim();
+    if name.ends_with(".bib") {
+        base_path.join(name)
+    } else {
+        // like BibTeX, any other dot is part of the name, e.g., in `refs.v2`
+        base_path.join(format!("{name}.bib"))
+    }
 }
 
 impl Iterator for CiteKeyIter {
@@ -37,13 +65,21 @@ impl Iterator for CiteKeyIter {
             };
             match aux_info {
                 AuxFileInfo::CiteKey(key) => return Some(Ok(key)),
+                AuxFileInfo::BibData(names) => {
+                    self.bib_files.extend(
+                        names
+                            .split(',')
+                            .filter(|name| !name.trim().is_empty())
+                            .map(|name| bib_path(&self.base_path, name)),
+                    );
+                }
                 AuxFileInfo::Input(path) => {
                     if !self.follow_inputs {
                         continue;
                     }
                     let mut aux_file = self.base_path.clone();
                     aux_file.push(&path);
-                    let aux_file = match fs::File::open(&aux_file) {
+                    let file = match fs::File::open(&aux_file) {
                         Ok(f) => f,
                         Err(error) => {
                             // soft fail of error when reading input file
@@ -54,7 +90,8 @@ impl Iterator for CiteKeyIter {
                             continue;
                         }
                     };
-                    let aux_iter = AuxFileIter::new(io::BufReader::new(aux_file));
+                    self.aux_files.push(aux_file);
+                    let aux_iter = AuxFileIter::new(io::BufReader::new(file));
                     self.aux_stack.push(aux_iter);
                 }
             }
@@ -65,6 +102,7 @@ impl Iterator for CiteKeyIter {
 enum AuxFileInfo {
     CiteKey(String),
     Input(camino::Utf8PathBuf),
+    BibData(String),
 }
 
 struct AuxFileIter<R> {
@@ -132,6 +170,16 @@ impl<R: io::BufRead> Iterator for AuxFileIter<R> {
                                 std::mem::take(&mut self.buffer),
                             ))));
                         }
+                        // bibtex databases
+                        if self.buffer.starts_with(r"\bibdata{") {
+                            self.buffer.drain(..9);
+                            self.buffer.drain(self.buffer.trim_end().len()..);
+                            debug_assert_eq!(self.buffer.chars().next_back(), Some('}'));
+                            self.buffer.pop();
+                            return Some(Ok(AuxFileInfo::BibData(std::mem::take(
+                                &mut self.buffer,
+                            ))));
+                        }
                         // skip all other lines
                         self.buffer.clear();
                     }
@@ -142,3 +190,43 @@ impl<R: io::BufRead> Iterator for AuxFileIter<R> {
         Some(Ok(AuxFileInfo::CiteKey(self.yield_citekey())))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AuxFileInfo, AuxFileIter};
+
+    #[test]
+    fn aux_file() {
+        let aux = r"\relax
+\citation{DBLP:journals/jair/JabsBNJ24,knuth1984}
+\abx@aux@cite{0}{DBLP:conf/cp/JabsBIJ23}
+\@input{chapter.aux}
+\bibdata{refs,dblp}
+";
+        let infos: Vec<_> = AuxFileIter::new(aux.as_bytes())
+            .map(|info| match info.unwrap() {
+                AuxFileInfo::CiteKey(key) => format!("cite {key}"),
+                AuxFileInfo::Input(path) => format!("input {path}"),
+                AuxFileInfo::BibData(names) => format!("bibdata {names}"),
+            })
+            .collect();
+        assert_eq!(
+            infos,
+            [
+                "cite DBLP:journals/jair/JabsBNJ24",
+                "cite knuth1984",
+                "cite DBLP:conf/cp/JabsBIJ23",
+                "input chapter.aux",
+                "bibdata refs,dblp",
+            ]
+        );
+    }
+
+    #[test]
+    fn bib_path() {
+        let base = camino::Utf8Path::new("paper");
+        assert_eq!(super::bib_path(base, "dblp"), "paper/dblp.bib");
+        assert_eq!(super::bib_path(base, "dblp.bib"), "paper/dblp.bib");
+        assert_eq!(super::bib_path(base, "refs.v2"), "paper/refs.v2.bib");
+    }
+}
@@ -0,0 +1,220 @@
+//! # Biblatex Control Files
+//!
+//! The `.bcf` file is written by biblatex for biber and contains the authoritative list of
+//! citations, split by refsection.
+
+use std::{fs, io};
+
+use quick_xml::events::Event;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("XML parsing error: {0}")]
+    Xml(#[from] quick_xml::Error),
+    #[error("invalid refsection number `{0}`")]
+    SectionNumber(String),
+}
+
+/// The citations of a single biblatex refsection
+#[derive(Clone, Debug, Default)]
+pub struct Section {
+    pub number: u32,
+    pub keys: Vec<String>,
+    /// Whether the section contains `\nocite{*}`
+    pub nocite_all: bool,
+}
+
+/// A bibliography data source of a refsection
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Datasource {
+    pub section: u32,
+    pub path: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ControlFile {
+    pub sections: Vec<Section>,
+    pub datasources: Vec<Datasource>,
+}
+
+impl ControlFile {
+    pub fn read<P: AsRef<camino::Utf8Path>>(path: P) -> Result<Self, Error> {
+        let file = fs::File::open(path.as_ref().as_std_path())?;
+        Self::parse(io::BufReader::new(file))
+    }
+
+    pub fn parse<R: io::BufRead>(reader: R) -> Result<Self, Error> {
+        let mut reader = quick_xml::Reader::from_reader(reader);
+        reader.config_mut().trim_text(true);
+
+        let mut control = ControlFile::default();
+        let mut section: Option<Section> = None;
+        let mut in_citekey = false;
+        let mut datasource_section: Option<u32> = None;
+        let mut in_datasource = false;
+        let mut buf = vec![];
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(elem) if elem.local_name().as_ref() == b"bibdata" => {
+                    datasource_section = Some(section_number(&elem, "section")?);
+                }
+                Event::End(elem) if elem.local_name().as_ref() == b"bibdata" => {
+                    datasource_section = None;
+                }
+                Event::Start(elem) if elem.local_name().as_ref() == b"datasource" => {
+                    let is_file = elem
+                        .try_get_attribute("type")
+                        .map_err(quick_xml::Error::from)?
+                        .is_none_or(|attr| attr.value.as_ref() == b"file");
+                    in_datasource = is_file && datasource_section.is_some();
+                }
+                Event::End(elem) if elem.local_name().as_ref() == b"datasource" => {
+                    in_datasource = false;
+                }
+                Event::Text(text) if in_datasource => {
+                    let section = datasource_section.expect("only in datasource inside bibdata");
+                    control.datasources.push(Datasource {
+                        section,
+                        path: unescape_text(&text)?,
+                    });
+                }
+                Event::Start(elem) if elem.local_name().as_ref() == b"section" => {
+                    let number = section_number(&elem, "number")?;
+                    section = Some(Section {
+                        number,
+                        ..Default::default()
+                    });
+                }
+                Event::End(elem) if elem.local_name().as_ref() == b"section" => {
+                    if let Some(section) = section.take() {
+                        control.sections.push(section);
+                    }
+                }
+                Event::Start(elem) if elem.local_name().as_ref() == b"citekey" => {
+                    in_citekey = section.is_some();
+                }
+                Event::End(elem) if elem.local_name().as_ref() == b"citekey" => {
+                    in_citekey = false;
+                }
+                Event::Text(text) if in_citekey => {
+                    let section = section.as_mut().expect("only in citekey inside section");
+                    let key = unescape_text(&text)?;
+                    if key == "*" {
+                        section.nocite_all = true;
+                    } else {
+                        section.keys.push(key);
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        Ok(control)
+    }
+
+    /// Iterates over the paths of all bibliography data sources, optionally restricted to a single
+    /// refsection
+    pub fn datasources(&self, refsection: Option<u32>) -> impl Iterator<Item = &str> {
+        self.datasources
+            .iter()
+            .filter(move |src| refsection.is_none_or(|num| src.section == num))
+            .map(|src| src.path.as_str())
+    }
+
+    /// Iterates over the sections, optionally restricted to a single refsection
+    fn sections(&self, refsection: Option<u32>) -> impl Iterator<Item = &Section> {
+        self.sections
+            .iter()
+            .filter(move |sec| refsection.is_none_or(|num| sec.number == num))
+    }
+
+    /// Iterates over all cited keys, optionally restricted to a single refsection
+    pub fn cite_keys(&self, refsection: Option<u32>) -> impl Iterator<Item = &str> {
+        self.sections(refsection)
+            .flat_map(|sec| sec.keys.iter().map(String::as_str))
+    }
+
+    /// Whether all entries of the bibliography are cited via `\nocite{*}`
+    pub fn nocite_all(&self, refsection: Option<u32>) -> bool {
+        self.sections(refsection).any(|sec| sec.nocite_all)
+    }
+}
+
+fn unescape_text(text: &quick_xml::events::BytesText) -> Result<String, Error> {
+    let text = text.decode().map_err(quick_xml::Error::from)?;
+    Ok(quick_xml::escape::unescape(&text)
+        .map_err(quick_xml::Error::from)?
+        .into_owned())
+}
+
+/// Parses a refsection number from an attribute of an element, defaulting to section 0
+fn section_number(elem: &quick_xml::events::BytesStart, attr: &str) -> Result<u32, Error> {
+    match elem
+        .try_get_attribute(attr)
+        .map_err(quick_xml::Error::from)?
+    {
+        Some(attr) => {
+            let number = attr.unescape_value()?;
+            number
+                .parse()
+                .map_err(|_| Error::SectionNumber(number.into_owned()))
+        }
+        None => Ok(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    const BCF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<bcf:controlfile version="3.11" bltxversion="3.20" xmlns:bcf="https://sourceforge.net/projects/biblatex">
+  <bcf:options component="biber" type="global">
+    <bcf:option type="singlevalued">
+      <bcf:key>output_encoding</bcf:key>
+      <bcf:value>utf8</bcf:value>
+    </bcf:option>
+  </bcf:options>
+  <!-- SECTION 0 -->
+  <bcf:bibdata section="0">
+    <bcf:datasource type="file" datatype="bibtex" glob="false">dblp.bib</bcf:datasource>
+  </bcf:bibdata>
+  <bcf:section number="0">
+    <bcf:citekey order="1" intorder="1">DBLP:journals/jair/JabsBNJ24</bcf:citekey>
+    <bcf:citekey order="2" intorder="1">knuth1984</bcf:citekey>
+  </bcf:section>
+  <!-- SECTION 1 -->
+  <bcf:section number="1">
+    <bcf:citekey order="1" intorder="1">DBLP:conf/cp/JabsBIJ23</bcf:citekey>
+    <bcf:citekey order="2" intorder="1">*</bcf:citekey>
+  </bcf:section>
+</bcf:controlfile>
+"#;
+
+    #[test]
+    fn sections() {
+        let control = super::ControlFile::parse(BCF.as_bytes()).unwrap();
+        assert_eq!(control.sections.len(), 2);
+
+        let keys: Vec<_> = control.cite_keys(None).collect();
+        assert_eq!(
+            keys,
+            [
+                "DBLP:journals/jair/JabsBNJ24",
+                "knuth1984",
+                "DBLP:conf/cp/JabsBIJ23"
+            ]
+        );
+        assert!(control.nocite_all(None));
+
+        let sources: Vec<_> = control.datasources(None).collect();
+        assert_eq!(sources, ["dblp.bib"]);
+        assert_eq!(control.datasources(Some(1)).count(), 0);
+
+        let keys: Vec<_> = control.cite_keys(Some(0)).collect();
+        assert_eq!(keys, ["DBLP:journals/jair/JabsBNJ24", "knuth1984"]);
+        assert!(!control.nocite_all(Some(0)));
+        assert!(control.nocite_all(Some(1)));
+    }
+}
@@ -0,0 +1,193 @@
+//! # LaTeX Sources
+//!
+//! Scans `.tex` sources for citations directly, without requiring a compiled `.aux` file. This
+//! follows `\input`, `\include` and `\subfile` and recognizes the common citation macros of
+//! LaTeX, natbib and biblatex.
+
+use std::{collections::HashSet, fs, io};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::citations::Citations;
+
+lazy_static! {
+    static ref COMMAND_PATTERN: Regex =
+        Regex::new(r"\\(?P<cmd>[A-Za-z]+)\*?(?:\s*\[[^\]]*\]){0,2}\s*\{(?P<arg>[^{}]*)\}").unwrap();
+}
+
+/// Citation macros whose mandatory argument is a comma-separated list of keys
+const CITE_COMMANDS: &[&str] = &[
+    "cite",
+    "Cite",
+    "citep",
+    "Citep",
+    "citet",
+    "Citet",
+    "citealp",
+    "citealt",
+    "citeauthor",
+    "citeyear",
+    "autocite",
+    "Autocite",
+    "textcite",
+    "Textcite",
+    "parencite",
+    "Parencite",
+    "footcite",
+    "smartcite",
+    "supercite",
+    "fullcite",
+    "nocite",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Command {
+    Cite(String),
+    Input(String),
+    Subfile(String),
+    Bibliography(String),
+}
+
+/// Scans a `.tex` file and all files it includes for citations
+pub fn scan<P: AsRef<camino::Utf8Path>>(tex_file: P) -> io::Result<Citations> {
+    let tex_file = tex_file.as_ref();
+    let mut base_path = tex_file.to_path_buf();
+    base_path.pop();
+
+    let mut citations = Citations::default();
+    let mut visited = HashSet::new();
+    let mut stack = vec![(tex_file.to_path_buf(), true)];
+    while let Some((path, is_root)) = stack.pop() {
+        if !visited.insert(path.clone()) {
+            continue;
+        }
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            // the root file not existing is a hard error
+            Err(error) if is_root => return Err(error),
+            Err(error) => {
+                // soft fail of error when reading input file
+                crate::cli::warning!("LaTeX", "failed to read input file `{path}`: `{error}`");
+                continue;
+            }
+        };
+        citations.files.push(path.clone());
+        let mut dir = path.clone();
+        dir.pop();
+        let mut inputs = vec![];
+        for command in commands(&content) {
+            match command {
+                Command::Cite(key) if key == "*" => citations.nocite_all = true,
+                Command::Cite(key) => citations.keys.push(key),
+                Command::Input(input) => inputs.push(tex_path(&base_path, &input)),
+                // subfiles are resolved relative to the including file
+                Command::Subfile(input) => inputs.push(tex_path(&dir, &input)),
+                Command::Bibliography(name) => {
+                    citations.bib_files.push(super::bib_path(&base_path, &name))
+                }
+            }
+        }
+        // push in reverse to visit inputs in document order
+        stack.extend(inputs.into_iter().rev().map(|path| (path, false)));
+    }
+    Ok(citations)
+}
+
+fn tex_path(dir: &camino::Utf8Path, input: &str) -> camino::Utf8PathBuf {
+    let mut path = dir.join(input.trim());
+    if path.extension().is_none() {
+        path.set_extension("tex");
+    }
+    path
+}
+
+/// Removes the comment from a line, if any
+fn strip_comment(line: &str) -> &str {
+    let mut escaped = false;
+    for (idx, char) in line.char_indices() {
+        match char {
+            '\\' => escaped = !escaped,
+            '%' if !escaped => return &line[..idx],
+            _ => escaped = false,
+        }
+    }
+    line
+}
+
+fn commands(content: &str) -> Vec<Command> {
+    let content: String = content
+        .lines()
+        .map(strip_comment)
+        .flat_map(|line| [line, "\n"])
+        .collect();
+    let mut commands = vec![];
+    for captures in COMMAND_PATTERN.captures_iter(&content) {
+        let arg = &captures["arg"];
+        match &captures["cmd"] {
+            "input" | "include" => commands.push(Command::Input(String::from(arg))),
+            "subfile" => commands.push(Command::Subfile(String::from(arg))),
+            "bibliography" | "addbibresource" => commands.extend(
+                arg.split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(|name| Command::Bibliography(String::from(name))),
+            ),
+            cmd if CITE_COMMANDS.contains(&cmd) => commands.extend(
+                arg.split(',')
+                    .map(str::trim)
+                    .filter(|key| !key.is_empty())
+                    .map(|key| Command::Cite(String::from(key))),
+            ),
+            _ => {}
+        }
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Command;
+
+    #[test]
+    fn citations() {
+        let content = r"
+\documentclass{article}
+\begin{document}
+As shown by \citet{DBLP:journals/jair/JabsBNJ24}, and others~\citep[see][p.~5]{DBLP:conf/cp/JabsBIJ23,
+  knuth1984}.
+% \cite{DBLP:conf/commented/Out}
+We save 100\% of \autocite[12]{DBLP:conf/sat/DaviesB13} % \cite{trailing}
+\textcite*{DBLP:conf/tacas/JabsBBJ25}
+\input{sections/intro}
+\include{chapter}
+\subfile{appendix.tex}
+\nocite{*}
+\bibliography{refs,dblp}
+\end{document}
+";
+        assert_eq!(
+            super::commands(content),
+            [
+                Command::Cite(String::from("DBLP:journals/jair/JabsBNJ24")),
+                Command::Cite(String::from("DBLP:conf/cp/JabsBIJ23")),
+                Command::Cite(String::from("knuth1984")),
+                Command::Cite(String::from("DBLP:conf/sat/DaviesB13")),
+                Command::Cite(String::from("DBLP:conf/tacas/JabsBBJ25")),
+                Command::Input(String::from("sections/intro")),
+                Command::Input(String::from("chapter")),
+                Command::Subfile(String::from("appendix.tex")),
+                Command::Cite(String::from("*")),
+                Command::Bibliography(String::from("refs")),
+                Command::Bibliography(String::from("dblp")),
+            ]
+        );
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(super::strip_comment(r"text % comment"), "text ");
+        assert_eq!(super::strip_comment(r"100\% sure"), r"100\% sure");
+        assert_eq!(super::strip_comment(r"line\\% comment"), r"line\\");
+    }
+}
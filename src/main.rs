@@ -1,29 +1,61 @@
-use std::io::Write;
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+};
 
-use clap::Parser;
-use cli::{Color, CommonGetArgs, DblpServerArgs, GetAllArgs, GetArgs, SearchArgs};
+use citations::Citations;
+use clap::{CommandFactory, FromArgMatches};
+use cli::{Color, CommonGetArgs, DblpServerArgs, GetAllArgs, GetArgs, ImportArgs, SearchArgs};
 use color_eyre::eyre::{Result, WrapErr, bail};
-use dblp::Record;
+use dblp::{Record, record::External};
 use futures::{StreamExt, TryStreamExt, stream};
 use owo_colors::OwoColorize;
 
+mod aliases;
 mod bibtex;
+mod citations;
 mod cli;
+mod config;
 mod dblp;
+mod export;
 mod fixers;
+mod import;
 mod latex;
 mod serde_utils;
+mod venues;
+mod watch;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install()?;
-    let args = cli::Args::parse();
+    let matches = cli::Args::command().get_matches();
+    let mut args = cli::Args::from_arg_matches(&matches)?;
+    let config = config::Layered::load(&args, &matches)?;
+    config.apply(&mut args, &matches);
+    if let cli::Commands::Get(cli::GetArgs { common, .. })
+    | cli::Commands::GetAll(cli::GetAllArgs { common, .. })
+    | cli::Commands::Import(cli::ImportArgs { common, .. }) = &mut args.command
+    {
+        common.build_pipeline()?;
+        if let Some(path) = &common.explain_log {
+            std::fs::File::create(path)
+                .wrap_err_with(|| format!("Failed to create explain log `{path}`"))?;
+        }
+    }
     args.color.init();
 
     match args.command {
         cli::Commands::Search(search_args) => search(search_args, args.dblp, args.color).await,
         cli::Commands::Get(get_args) => get(get_args, args.dblp, args.color).await,
         cli::Commands::GetAll(get_all_args) => get_all(get_all_args, args.dblp, args.color).await,
+        cli::Commands::Import(import_args) => import(import_args, args.dblp, args.color).await,
+        cli::Commands::Fixers(cli::FixersCommands::List(fixer_args)) => {
+            list_fixers(fixer_args, args.color)
+        }
+        cli::Commands::Config(cli::ConfigCommands::Show) => {
+            print!("{}", config.display());
+            Ok(())
+        }
     }
 }
 
@@ -47,60 +79,180 @@ async fn search(args: SearchArgs, dblp: DblpServerArgs, color: Color) -> Result<
     Ok(())
 }
 
-fn fixup(rec: &mut dblp::Record, args: &CommonGetArgs) {
-    fixers::author_num(rec);
-    fixers::escape_latex(rec);
-    fixers::page_range(rec);
-    fixers::names(rec);
-    fixers::strip_title_period(rec);
-    fixers::capital_after_colon(rec);
-    fixers::proper_nouns(rec);
-    fixers::acronyms(rec);
-    fixers::weird_urls(rec);
-    fixers::date_ranges(rec);
-    fixers::dashes(rec);
-    fixers::manually_correct(rec);
-    if !args.unicode {
-        fixers::unicode(rec);
-    }
-    if !args.all_externals {
-        fixers::single_external(rec);
+fn fixup(rec: &mut dblp::Record, args: &CommonGetArgs, color: Color) {
+    let mut report = if args.explain || args.explain_log.is_some() {
+        fixers::pipeline::FixReport::explaining()
+    } else {
+        fixers::pipeline::FixReport::default()
+    };
+    args.pipeline.apply(rec, &mut report);
+    for note in report.notes() {
+        cli::warning!(note.fixer, "{}", note.message);
+    }
+    if report.changes().is_empty() {
+        return;
+    }
+    if args.explain {
+        let mut styles = cli::Styles::default();
+        if color.should_color(&std::io::stderr()) {
+            styles.colorize();
+        }
+        let mut stderr = std::io::stderr().lock();
+        let _ = writeln!(stderr, "{}", rec.key().style(styles.citekey));
+        for change in report.changes() {
+            let _ = writeln!(stderr, "  {change}");
+        }
+    }
+    if let Some(path) = &args.explain_log
+        && let Err(err) = log_changes(path, rec.key(), report.changes())
+    {
+        cli::warning!("Explain", "failed to write to `{path}`: {err}");
     }
 }
 
-async fn get(args: GetArgs, dblp: DblpServerArgs, color: Color) -> Result<()> {
-    let mut rec = dblp::Record::get(
-        &args.key,
-        !args.common.crossref,
-        !args.common.dont_expand_journals,
-        &dblp,
-    )
-    .await?;
-    fixup(&mut rec, &args.common);
-    let crossref = if let Some(key) = rec.crossref_key() {
-        let mut crossref = dblp::Record::get(key, !args.common.crossref, false, &dblp).await?;
-        fixup(&mut crossref, &args.common);
-        fixers::expand_booktitle(&mut rec, &crossref);
-        Some(crossref)
-    } else {
-        None
-    };
-    let mut bibtex = rec.bibtex();
+/// Appends the changes to a record as a JSON line to the log file
+fn log_changes(
+    path: &camino::Utf8Path,
+    key: &str,
+    changes: &[fixers::pipeline::Change],
+) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(path)?;
+    let line = serde_json::json!({ "key": key, "changes": changes });
+    writeln!(file, "{line}")
+}
+
+fn list_fixers(args: cli::FixerArgs, color: Color) -> Result<()> {
+    let mut pipeline = fixers::pipeline::Pipeline::builtin(
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    );
+    args.configure(&mut pipeline)?;
+    let mut styles = cli::Styles::default();
     if color.should_color(&std::io::stdout()) {
-        bibtex.colorize();
+        styles.colorize();
     }
-    println!("{bibtex}");
-    if let Some(rec) = crossref {
+    for (fixer, enabled) in pipeline.fixers() {
+        let state = if enabled { "enabled" } else { "disabled" };
+        print!(
+            "{:<20} {:<9} {}",
+            fixer.name().style(styles.citekey),
+            state,
+            fixer.description()
+        );
+        if !fixer.after().is_empty() {
+            print!(" (after {})", fixer.after().join(", "));
+        }
         println!();
-        let mut bibtex = rec.bibtex();
-        if color.should_color(&std::io::stdout()) {
-            bibtex.colorize();
+    }
+    Ok(())
+}
+
+/// Reads keys from a file or stdin, one per line, skipping empty lines and `#` comments
+fn read_keys(path: &camino::Utf8Path) -> Result<Vec<String>> {
+    let content = if path == "-" {
+        std::io::read_to_string(std::io::stdin()).wrap_err("Failed to read keys from stdin")?
+    } else {
+        std::fs::read_to_string(path).wrap_err_with(|| format!("Failed to read `{path}`"))?
+    };
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect())
+}
+
+/// Resolves a key given to `get` to a DBLP key without prefix, looking up DOIs on DBLP
+async fn resolve_key<Service>(
+    input: &str,
+    doi: bool,
+    dblp: &DblpServerArgs,
+    service: &mut Service,
+    opts: &CommonGetArgs,
+) -> Result<String>
+where
+    Service: tower::Service<
+            reqwest::Request,
+            Response = reqwest::Response,
+            Error = Box<dyn std::error::Error + Send + std::marker::Sync + 'static>,
+        >,
+{
+    match External::from(String::from(input)) {
+        External::Doi(doi) => {
+            let Some(key) = dblp::search::doi_key(&doi, dblp, service)
+                .await
+                .wrap_err_with(|| format!("Failed to search DBLP for DOI `{doi}`"))?
+            else {
+                bail!("DOI `{doi}` is not in DBLP");
+            };
+            Ok(key)
+        }
+        External::Url(value) if doi => bail!("`{value}` is not a DOI"),
+        External::Url(_) => {
+            let key = input
+                .strip_prefix(opts.key_prefix.as_str())
+                .unwrap_or(input);
+            Ok(key.parse::<dblp::DblpKey>()?.to_string())
+        }
+    }
+}
+
+async fn get(args: GetArgs, dblp: DblpServerArgs, color: Color) -> Result<()> {
+    let mut inputs: Vec<(String, bool)> = args.keys.into_iter().map(|key| (key, false)).collect();
+    if let Some(path) = &args.from_file {
+        inputs.extend(read_keys(path)?.into_iter().map(|key| (key, false)));
+    }
+    inputs.extend(args.doi.map(|doi| (doi, true)));
+
+    let mut service = dblp::new_service(&dblp);
+    let resolved: Vec<_> = stream::iter(&inputs)
+        .map(|(input, doi)| {
+            let mut service = service.clone();
+            let dblp = &dblp;
+            let common = &args.common;
+            async move { resolve_key(input, *doi, dblp, &mut service, common).await }
+        })
+        .buffered(dblp.concurrent_requests)
+        .collect()
+        .await;
+    // inputs that fail to resolve are reported after writing the others, like unknown keys
+    let mut unresolved = vec![];
+    let mut keys = vec![];
+    for res in resolved {
+        match res {
+            Ok(key) => keys.push(key),
+            Err(err) => unresolved.push(format!("{err:#}")),
+        }
+    }
+    keys.sort_unstable();
+    keys.dedup();
+
+    let fetched = fetch_with_crossrefs(&keys, &dblp, &mut service, &args.common, color).await?;
+    if !fetched.records.is_empty() || !fetched.crossref_recs.is_empty() {
+        write_records(
+            fetched.records.iter().chain(&fetched.crossref_recs),
+            &args.common,
+            args.output.as_deref(),
+            color,
+        )?;
+    }
+    if !unresolved.is_empty() {
+        if !fetched.unknown_keys.is_empty() {
+            cli::warning!("DBLP", "unknown DBLP keys:{}", fetched.unknown_keys);
         }
-        println!("{bibtex}");
+        bail!("failed to resolve keys:{}", key_list(&unresolved));
+    }
+    if !fetched.unknown_keys.is_empty() {
+        bail!("unknown DBLP keys:{}", fetched.unknown_keys);
     }
     Ok(())
 }
 
+#[allow(clippy::large_enum_variant)]
 enum FetchRes {
     Rec(dblp::Record),
     Unknown(String),
@@ -111,6 +263,7 @@ async fn fetch_record<Service>(
     dblp: &DblpServerArgs,
     service: &mut Service,
     opts: &cli::CommonGetArgs,
+    color: Color,
 ) -> Result<FetchRes, dblp::record::Error>
 where
     Service: tower::Service<
@@ -123,6 +276,7 @@ where
         key,
         !opts.crossref,
         !opts.dont_expand_journals,
+        opts.venue_style,
         dblp,
         service,
     )
@@ -134,7 +288,7 @@ where
             err => return Err(err),
         },
     };
-    fixup(&mut rec, opts);
+    fixup(&mut rec, opts, color);
     Ok(FetchRes::Rec(rec))
 }
 
@@ -142,7 +296,7 @@ async fn fetch_keys<Service>(
     keys: &[String],
     dblp: &DblpServerArgs,
     service: &mut Service,
-    opts: &cli::GetAllArgs,
+    opts: &cli::CommonGetArgs,
     color: Color,
 ) -> Result<Vec<FetchRes>>
 where
@@ -152,6 +306,11 @@ where
             Error = Box<dyn std::error::Error + Send + std::marker::Sync + 'static>,
         > + Clone,
 {
+    // nothing to report, e.g., if no record has a crossref
+    if keys.is_empty() {
+        return Ok(vec![]);
+    }
+
     // Setup progress information
     let err_styles = {
         let mut styles = cli::Styles::default();
@@ -179,7 +338,7 @@ where
                 bar.set_message(key.clone());
             }
             let res = async move {
-                fetch_record(key, dblp, &mut service, &opts.common)
+                fetch_record(key, dblp, &mut service, opts, color)
                     .await
                     .wrap_err_with(|| format!("Failed to fetch record `{key}`"))
             };
@@ -195,70 +354,363 @@ where
     Ok(results)
 }
 
-async fn get_all(mut args: GetAllArgs, dblp: DblpServerArgs, color: Color) -> Result<()> {
+/// Records fetched together with the records they crossref
+struct Fetched {
+    records: Vec<Record>,
+    crossref_recs: Vec<Record>,
+    /// Keys that are not in DBLP, as list items
+    unknown_keys: String,
+}
+
+/// Fetches records and the records they crossref, where each record is only fetched once
+///
+/// Records that are crossrefed by others are moved to the crossrefs. If requested, booktitles are
+/// expanded from the crossrefs.
+async fn fetch_with_crossrefs<Service>(
+    keys: &[String],
+    dblp: &DblpServerArgs,
+    service: &mut Service,
+    opts: &CommonGetArgs,
+    color: Color,
+) -> Result<Fetched>
+where
+    Service: tower::Service<
+            reqwest::Request,
+            Response = reqwest::Response,
+            Error = Box<dyn std::error::Error + Send + std::marker::Sync + 'static>,
+        > + Clone,
+{
+    let mut unknown_keys = String::new();
+    let mut unknown = |key: String| {
+        unknown_keys.push_str("\n- ");
+        unknown_keys.push_str(&key);
+    };
+
+    let results = fetch_keys(keys, dblp, service, opts, color).await?;
+    let mut records: Vec<_> = results
+        .into_iter()
+        .filter_map(|res| match res {
+            FetchRes::Rec(rec) => Some(rec),
+            FetchRes::Unknown(key) => {
+                unknown(key);
+                None
+            }
+        })
+        .collect();
+    records.sort_unstable_by(|a, b| a.key().cmp(b.key()));
+
+    let mut crossref_keys: Vec<String> = records
+        .iter()
+        .filter_map(|rec| rec.crossref_key().map(str::to_owned))
+        .collect();
+    crossref_keys.sort_unstable();
+    crossref_keys.dedup();
+
+    // remove crossref keys we already downloaded
+    let mut crossref_recs = Vec::with_capacity(crossref_keys.len());
+    crossref_keys.retain(|key| {
+        let Ok(idx) = records.binary_search_by_key(&key.as_str(), Record::key) else {
+            return true;
+        };
+        crossref_recs.push(records.remove(idx));
+        false
+    });
+
+    let results = fetch_keys(&crossref_keys, dblp, service, opts, color).await?;
+    crossref_recs.extend(results.into_iter().filter_map(|res| match res {
+        FetchRes::Rec(rec) => Some(rec),
+        FetchRes::Unknown(key) => {
+            unknown(key);
+            None
+        }
+    }));
+    crossref_recs.sort_unstable_by(|a, b| a.key().cmp(b.key()));
+
+    if opts.crossref {
+        for rec in &mut records {
+            if let Some(key) = rec.crossref_key()
+                && let Ok(idx) = crossref_recs.binary_search_by_key(&key, Record::key)
+            {
+                fixers::expand_booktitle(rec, &crossref_recs[idx], opts.venue_style);
+            }
+        }
+    }
+
+    Ok(Fetched {
+        records,
+        crossref_recs,
+        unknown_keys,
+    })
+}
+
+/// Collects the cited keys
+///
+/// Pandoc Markdown and Typst documents are selected by their file extension. For LaTeX documents,
+/// the keys are read from the `.tex` sources if requested, from the `.bcf` file if present, or
+/// the `.aux` file otherwise.
+fn cited_keys(args: &mut GetAllArgs) -> Result<Citations> {
+    match args.latex_path.extension() {
+        Some("md" | "markdown") => {
+            return citations::pandoc::scan(&args.latex_path)
+                .wrap_err_with(|| format!("Failed to scan `{}`", args.latex_path));
+        }
+        Some("typ") => {
+            return citations::typst::scan(&args.latex_path)
+                .wrap_err_with(|| format!("Failed to scan `{}`", args.latex_path));
+        }
+        _ => {}
+    }
+    if args.from_tex {
+        args.latex_path.set_extension("tex");
+        return latex::tex::scan(&args.latex_path)
+            .wrap_err_with(|| format!("Failed to scan `{}`", args.latex_path));
+    }
+    let bcf_path = args.latex_path.with_extension("bcf");
+    if bcf_path.exists() {
+        let control = latex::bcf::ControlFile::read(&bcf_path)
+            .wrap_err_with(|| format!("Failed to read `{bcf_path}`"))?;
+        let base_path = bcf_path.parent().unwrap_or(camino::Utf8Path::new(""));
+        return Ok(Citations {
+            keys: control
+                .cite_keys(args.refsection)
+                .map(String::from)
+                .collect(),
+            nocite_all: control.nocite_all(args.refsection),
+            bib_files: control
+                .datasources(args.refsection)
+                .map(|path| base_path.join(path))
+                .collect(),
+            files: vec![bcf_path],
+        });
+    }
     args.latex_path.set_extension("aux");
-    let keys: Result<Vec<_>, _> =
-        latex::CiteKeyIter::new(&args.latex_path, !args.no_follow_inputs)?
-            .filter(|res| {
-                res.as_ref().is_err() || res.as_ref().is_ok_and(|key| key.starts_with("DBLP:"))
-            })
+    let mut iter = latex::CiteKeyIter::new(&args.latex_path, !args.no_follow_inputs)?;
+    let keys: Result<Vec<_>, _> = iter.by_ref().collect();
+    Ok(Citations {
+        keys: keys?,
+        nocite_all: false,
+        bib_files: iter.bib_files().to_vec(),
+        files: iter.aux_files().to_vec(),
+    })
+}
+
+/// Checks whether two paths refer to the same file, also if the files do not exist yet
+fn same_file(a: &camino::Utf8Path, b: &camino::Utf8Path) -> bool {
+    match (a.canonicalize_utf8(), b.canonicalize_utf8()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Chooses the BibTeX file to write to, if not given, and checks that it is referenced by the
+/// document
+///
+/// Formats not read by TeX are only written to an explicitly given file that is not a BibTeX file,
+/// and to stdout otherwise, so that they never overwrite the bibliography of the document.
+fn choose_bibtex_path(
+    args: &mut GetAllArgs,
+    bib_files: &[camino::Utf8PathBuf],
+    color: Color,
+) -> Result<()> {
+    if !args.common.output_format.is_tex() {
+        if let Some(path) = &args.bibtex_path
+            && (path.extension() == Some("bib") || bib_files.iter().any(|bib| same_file(bib, path)))
+        {
+            bail!(
+                "refusing to overwrite the BibTeX file `{path}` with output in a format not read by TeX, write to a file with another extension or to stdout instead"
+            );
+        }
+        return Ok(());
+    }
+    if let Some(path) = &args.bibtex_path {
+        if !bib_files.iter().any(|bib| same_file(bib, path)) {
+            cli::warning!(
+                "BibTeX",
+                "`{path}` is not referenced as a bibliography database by the document"
+            );
+        }
+        return Ok(());
+    }
+    args.bibtex_path = bib_files
+        .iter()
+        .find(|path| path.file_stem() == Some(args.bib_name.as_str()))
+        .cloned();
+    if let Some(path) = &args.bibtex_path {
+        let mut styles = cli::Styles::default();
+        if color.should_color(&std::io::stderr()) {
+            styles.colorize();
+        }
+        eprintln!("{} `{path}`", "using BibTeX file".style(styles.info));
+    }
+    Ok(())
+}
+
+/// Which records are still used, i.e., cited or the crossref target of a cited record
+struct Usage<'a> {
+    /// The sorted cited keys
+    cited: &'a [String],
+    /// Whether all entries are cited with `\nocite{*}`
+    cited_all: bool,
+    /// The sorted crossref keys of the cited records
+    crossref_keys: Vec<String>,
+}
+
+impl<'a> Usage<'a> {
+    fn new(records: &[Record], cited: &'a [String], cited_all: bool) -> Self {
+        let mut usage = Self {
+            cited,
+            cited_all,
+            crossref_keys: vec![],
+        };
+        usage.crossref_keys = records
+            .iter()
+            .filter(|rec| usage.is_cited(rec))
+            .filter_map(|rec| rec.crossref_key().map(str::to_owned))
             .collect();
-    let mut keys = keys?;
-    keys.sort_unstable();
-    keys.dedup();
+        usage.crossref_keys.sort_unstable();
+        usage.crossref_keys.dedup();
+        usage
+    }
+
+    fn is_cited(&self, rec: &Record) -> bool {
+        self.cited_all
+            || self
+                .cited
+                .binary_search_by(|key| key.as_str().cmp(rec.key()))
+                .is_ok()
+    }
+
+    fn is_used(&self, rec: &Record) -> bool {
+        self.is_cited(rec)
+            || self
+                .crossref_keys
+                .binary_search_by(|key| key.as_str().cmp(rec.key()))
+                .is_ok()
+    }
+}
+
+fn report_unused<'a>(
+    unused: impl Iterator<Item = &'a Record>,
+    prefix: &str,
+    pruned: bool,
+    color: Color,
+) {
+    let styles = {
+        let mut styles = cli::Styles::default();
+        if color.should_color(&std::io::stderr()) {
+            styles.colorize();
+        }
+        styles
+    };
+    let mut unused = unused.peekable();
+    if unused.peek().is_none() {
+        return;
+    }
+    if pruned {
+        eprintln!("{}", "removing unused DBLP entries".style(styles.info));
+    } else {
+        eprintln!("{}", "unused DBLP entries".style(styles.info));
+    }
+    for rec in unused {
+        eprintln!("- {prefix}{}", rec.key().style(styles.citekey));
+    }
+}
 
+async fn get_all(mut args: GetAllArgs, dblp: DblpServerArgs, color: Color) -> Result<()> {
+    let Citations {
+        keys: cited_keys,
+        nocite_all: cited_all,
+        bib_files,
+        files,
+    } = cited_keys(&mut args)?;
+    choose_bibtex_path(&mut args, &bib_files, color)?;
+    if args.watch && args.bibtex_path.is_none() {
+        bail!("watch mode requires a file to write to");
+    }
+    let aliases = match &args.aliases {
+        Some(path) => aliases::Aliases::read(path, &args.common.key_prefix)
+            .wrap_err_with(|| format!("Failed to read alias file `{path}`"))?,
+        None => aliases::Aliases::default(),
+    };
+    let aliases::Resolved {
+        mut keys,
+        aliases: cited_aliases,
+    } = aliases.resolve(
+        cited_keys.iter().map(String::as_str),
+        &args.common.key_prefix,
+    );
+
+    // only BibTeX output can be read back in
     let mut records = if !args.dont_reuse_existing
+        && args.common.output_format.is_tex()
         && let Some(bibtex_path) = &args.bibtex_path
+        && bibtex_path.exists()
     {
         let content = std::fs::read_to_string(bibtex_path)?;
-        bibtex::parse(&content)?
+        bibtex::parse(&content, &args.common.key_prefix)?
     } else {
         vec![]
     };
     records.sort_unstable_by(|a, b| a.key().cmp(b.key()));
     for rec in &mut records {
-        fixup(rec, &args.common);
+        if args.common.unicode || !args.common.output_format.is_tex() {
+            fixers::latex_to_unicode(rec);
+        }
+        fixup(rec, &args.common, color);
     }
 
+    // Keys cited in the document, used to determine unused entries
+    let cited = keys.clone();
+
     // Remove keys that are already present
     let mut idx = 0;
     keys.retain(|key| {
-        let key = key.strip_prefix("DBLP:").unwrap_or(key);
-        while idx < records.len() && *records[idx].key() < *key {
+        while idx < records.len() && records[idx].key() < key.as_str() {
             idx += 1;
         }
-        if idx < records.len() && *records[idx].key() == *key {
+        if idx < records.len() && records[idx].key() == key {
             return false;
         }
         true
     });
 
+    let invalid_keys = split_invalid(&mut keys);
+
     let mut service = dblp::new_service(&dblp);
 
-    let results = fetch_keys(&keys, &dblp, &mut service, &args, color).await?;
+    let results = fetch_keys(&keys, &dblp, &mut service, &args.common, color).await?;
 
-    let mut unknown_keys = String::new();
-    let mut crossref_keys = vec![];
-
-    for rec in &records {
-        if let Some(key) = rec.crossref_key() {
-            crossref_keys.push(key.to_owned());
-        }
-    }
+    let mut unknown_keys = vec![];
 
     records.extend(results.into_iter().filter_map(|res| match res {
-        FetchRes::Rec(rec) => {
-            if let Some(key) = rec.crossref_key() {
-                crossref_keys.push(key.to_owned());
-            }
-            Some(rec)
-        }
+        FetchRes::Rec(rec) => Some(rec),
         FetchRes::Unknown(key) => {
-            unknown_keys.push_str("\n- ");
-            unknown_keys.push_str(&key);
+            unknown_keys.push(key);
             None
         }
     }));
+    records.sort_unstable_by(|a, b| a.key().cmp(b.key()));
+
+    let mut crossref_keys: Vec<String> = records
+        .iter()
+        .filter_map(|rec| rec.crossref_key().map(str::to_owned))
+        .collect();
+
+    if args.prune || args.report_unused {
+        let usage = Usage::new(&records, &cited, cited_all);
+        if args.report_unused {
+            report_unused(
+                records.iter().filter(|rec| !usage.is_used(rec)),
+                &args.common.key_prefix,
+                args.prune,
+                color,
+            );
+        }
+        if args.prune {
+            records.retain(|rec| usage.is_used(rec));
+            crossref_keys = usage.crossref_keys;
+        }
+    }
 
     crossref_keys.sort_unstable();
     crossref_keys.dedup();
@@ -278,13 +730,12 @@ async fn get_all(mut args: GetAllArgs, dblp: DblpServerArgs, color: Color) -> Re
         true
     });
 
-    let results = fetch_keys(&crossref_keys, &dblp, &mut service, &args, color).await?;
+    let results = fetch_keys(&crossref_keys, &dblp, &mut service, &args.common, color).await?;
 
     crossref_recs.extend(results.into_iter().filter_map(|res| match res {
         FetchRes::Rec(record) => Some(record),
         FetchRes::Unknown(key) => {
-            unknown_keys.push_str("\n- ");
-            unknown_keys.push_str(&key);
+            unknown_keys.push(key);
             None
         }
     }));
@@ -299,35 +750,601 @@ async fn get_all(mut args: GetAllArgs, dblp: DblpServerArgs, color: Color) -> Re
                 let Ok(idx) = crossref_recs.binary_search_by_key(&key, Record::key) else {
                     bail!("crossref key not found: {key}");
                 };
-                fixers::expand_booktitle(rec, &crossref_recs[idx]);
+                fixers::expand_booktitle(rec, &crossref_recs[idx], args.common.venue_style);
             }
         }
     }
 
+    let entries = Entries {
+        records,
+        crossref_recs,
+        aliases: cited_aliases,
+        unknown: unknown_keys
+            .iter()
+            .cloned()
+            .chain(invalid_keys.iter().map(|invalid| invalid.0.clone()))
+            .collect(),
+    };
+    write_output(&args, &entries, color)?;
+
+    if args.watch {
+        if !unknown_keys.is_empty() {
+            cli::warning!("DBLP", "unknown DBLP keys:{}", key_list(&unknown_keys));
+        }
+        if !invalid_keys.is_empty() {
+            cli::warning!("DBLP", "invalid DBLP keys:{}", key_list(&invalid_keys));
+        }
+        return watch(args, files, &dblp, &mut service, aliases, entries, color).await;
+    }
+
+    if !invalid_keys.is_empty() {
+        if !unknown_keys.is_empty() {
+            cli::warning!("DBLP", "unknown DBLP keys:{}", key_list(&unknown_keys));
+        }
+        bail!("invalid DBLP keys:{}", key_list(&invalid_keys));
+    }
+    if !unknown_keys.is_empty() {
+        bail!("unknown DBLP keys:{}", key_list(&unknown_keys));
+    }
+    Ok(())
+}
+
+/// Formats keys as list items for error messages
+fn key_list<T: std::fmt::Display>(keys: &[T]) -> String {
+    keys.iter().map(|key| format!("\n- {key}")).collect()
+}
+
+/// Removes the keys that are not valid DBLP keys, e.g., mistyped citations with the DBLP prefix
+fn split_invalid(keys: &mut Vec<String>) -> Vec<dblp::InvalidKey> {
+    let mut invalid = vec![];
+    keys.retain(|key| match key.parse::<dblp::DblpKey>() {
+        Ok(_) => true,
+        Err(err) => {
+            invalid.push(err);
+            false
+        }
+    });
+    invalid
+}
+
+fn record_bibtex<'a>(
+    rec: &'a Record,
+    args: &'a CommonGetArgs,
+    aliases: &'a HashMap<String, String>,
+) -> dblp::record::Bibtex<'a> {
+    let mut bibtex = rec.bibtex();
+    bibtex
+        .prefix(&args.key_prefix)
+        .orcid(args.orcid)
+        .flavor(args.output_format.flavor().unwrap_or_default());
+    if let Some(alias) = aliases.get(rec.key()) {
+        bibtex.alias(alias);
+    }
+    bibtex
+}
+
+/// The entries written by `get-all`
+struct Entries {
+    records: Vec<Record>,
+    crossref_recs: Vec<Record>,
+    /// Maps DBLP keys to the alias key they are cited as
+    aliases: HashMap<String, String>,
+    /// Cited keys that are invalid or not in DBLP, which are not fetched again while watching
+    unknown: HashSet<String>,
+}
+
+fn write_output(args: &GetAllArgs, entries: &Entries, color: Color) -> Result<()> {
+    let all = || entries.records.iter().chain(&entries.crossref_recs);
+    if let Some(export) = args.common.output_format.export() {
+        let keyed = all().map(|rec| {
+            let key = match entries.aliases.get(rec.key()) {
+                Some(alias) => alias.clone(),
+                None => format!("{}{}", args.common.key_prefix, rec.key()),
+            };
+            (key, rec)
+        });
+        let out = export::render(export, keyed);
+        match &args.bibtex_path {
+            Some(bibtex_path) => std::fs::write(bibtex_path, out)?,
+            None => print!("{out}"),
+        }
+        return Ok(());
+    }
+    let strings = if args.string_macros {
+        bibtex::strings::Strings::collect(all())
+    } else {
+        Default::default()
+    };
+    let bibtex = |rec| {
+        let mut bibtex = record_bibtex(rec, &args.common, &entries.aliases);
+        bibtex.strings(&strings);
+        bibtex
+    };
     if let Some(bibtex_path) = &args.bibtex_path {
         let mut writer = std::fs::File::create(bibtex_path)?;
-        for (idx, rec) in records.into_iter().chain(crossref_recs).enumerate() {
+        if !strings.is_empty() {
+            writeln!(writer, "{strings}")?;
+        }
+        for (idx, rec) in all().enumerate() {
             if idx > 0 {
                 writeln!(writer)?;
             }
-            let bibtex = rec.bibtex();
+            let bibtex = bibtex(rec);
             writeln!(writer, "{bibtex}")?;
         }
     } else {
-        for (idx, rec) in records.into_iter().chain(crossref_recs).enumerate() {
+        if !strings.is_empty() {
+            println!("{strings}");
+        }
+        for (idx, rec) in all().enumerate() {
             if idx > 0 {
                 println!();
             }
-            let mut bibtex = rec.bibtex();
+            let mut bibtex = bibtex(rec);
             if color.should_color(&std::io::stdout()) {
                 bibtex.colorize();
             }
             println!("{bibtex}");
         }
     }
+    Ok(())
+}
 
-    if !unknown_keys.is_empty() {
-        bail!("unknown DBLP keys:{unknown_keys}");
+/// Writes records in the output format to a file, or to stdout if no file is given
+fn write_records<'a>(
+    records: impl IntoIterator<Item = &'a Record>,
+    common: &CommonGetArgs,
+    path: Option<&camino::Utf8Path>,
+    color: Color,
+) -> Result<()> {
+    let out = if let Some(export) = common.output_format.export() {
+        export::render(
+            export,
+            records
+                .into_iter()
+                .map(|rec| (format!("{}{}", common.key_prefix, rec.key()), rec)),
+        )
+    } else {
+        let no_aliases = HashMap::new();
+        let colorize = path.is_none() && color.should_color(&std::io::stdout());
+        let mut out = String::new();
+        for (idx, rec) in records.into_iter().enumerate() {
+            if idx > 0 {
+                out.push('\n');
+            }
+            let mut bibtex = record_bibtex(rec, common, &no_aliases);
+            if colorize {
+                bibtex.colorize();
+            }
+            out.push_str(&format!("{bibtex}\n"));
+        }
+        out
+    };
+    match path {
+        Some(path) => std::fs::write(path, out)?,
+        None => print!("{out}"),
     }
     Ok(())
 }
+
+/// The number of search hits considered as candidates for an imported entry
+const IMPORT_CANDIDATES: u32 = 10;
+
+/// Searches DBLP for an imported entry, by its DOI if it has one and otherwise by its title and
+/// first author
+async fn match_entry<Service>(
+    entry: &import::Entry,
+    dblp: &DblpServerArgs,
+    service: &mut Service,
+) -> Result<Option<import::matching::Match>, dblp::search::Error>
+where
+    Service: tower::Service<
+            reqwest::Request,
+            Response = reqwest::Response,
+            Error = Box<dyn std::error::Error + Send + std::marker::Sync + 'static>,
+        >,
+{
+    if let Some(doi) = &entry.doi {
+        let mut query = dblp::search::Query::new(dblp::search::Type::Publication, doi);
+        query.hits(IMPORT_CANDIDATES);
+        let response = query.get_with_service(dblp, service).await?;
+        if let Some(found) = import::matching::best(entry, response.iter_hits())
+            && found.by_doi
+        {
+            return Ok(Some(found));
+        }
+    }
+    let mut words = import::matching::words(&entry.title);
+    if words.is_empty() {
+        return Ok(None);
+    }
+    words.extend(entry.authors.first().cloned());
+    let mut query = dblp::search::Query::new(dblp::search::Type::Publication, words.join(" "));
+    query.hits(IMPORT_CANDIDATES);
+    let response = query.get_with_service(dblp, service).await?;
+    Ok(import::matching::best(entry, response.iter_hits()))
+}
+
+async fn import(args: ImportArgs, dblp: DblpServerArgs, color: Color) -> Result<()> {
+    let entries =
+        import::read(&args.path).wrap_err_with(|| format!("Failed to read `{}`", args.path))?;
+    let styles = {
+        let mut styles = cli::Styles::default();
+        if color.should_color(&std::io::stderr()) {
+            styles.colorize();
+        }
+        styles
+    };
+    eprintln!("{}", "matching entries to DBLP".style(styles.info));
+
+    let mut service = dblp::new_service(&dblp);
+    let matches: Vec<_> = stream::iter(&entries)
+        .map(|entry| {
+            let mut service = service.clone();
+            let dblp = &dblp;
+            async move {
+                match_entry(entry, dblp, &mut service)
+                    .await
+                    .wrap_err_with(|| format!("Failed to search DBLP for `{}`", entry.key))
+            }
+        })
+        .buffered(dblp.concurrent_requests)
+        .try_collect()
+        .await?;
+
+    // maps the original keys to DBLP keys without prefix
+    let mut renames = std::collections::BTreeMap::new();
+    for (entry, found) in entries.iter().zip(matches) {
+        let Some(found) = found else {
+            eprintln!("- {}: no candidates", entry.key.style(styles.citekey));
+            continue;
+        };
+        let reason = if found.by_doi {
+            String::from("DOI")
+        } else {
+            format!("{:.2}", found.score)
+        };
+        let accepted = found.confidence >= args.min_confidence;
+        eprintln!(
+            "- {} -> {}{} ({}, {reason}){}",
+            entry.key.style(styles.citekey),
+            args.common.key_prefix,
+            found.key.style(styles.citekey),
+            found.confidence,
+            if accepted { "" } else { ", not accepted" },
+        );
+        if accepted {
+            renames.insert(entry.key.clone(), found.key);
+        }
+    }
+
+    if let Some(path) = &args.rename_map {
+        let table: std::collections::BTreeMap<_, _> = renames
+            .iter()
+            .map(|(key, dblp)| (key, format!("{}{dblp}", args.common.key_prefix)))
+            .collect();
+        std::fs::write(path, toml::to_string(&table)?)
+            .wrap_err_with(|| format!("Failed to write rename map `{path}`"))?;
+    }
+
+    let mut keys: Vec<String> = renames.into_values().collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    let fetched = fetch_with_crossrefs(&keys, &dblp, &mut service, &args.common, color).await?;
+    if !fetched.unknown_keys.is_empty() {
+        cli::warning!("DBLP", "unknown DBLP keys:{}", fetched.unknown_keys);
+    }
+
+    write_records(
+        fetched.records.iter().chain(&fetched.crossref_recs),
+        &args.common,
+        args.output.as_deref(),
+        color,
+    )
+}
+
+/// Keeps the BibTeX file in sync with the citations of the document, fetching newly cited keys
+/// whenever the files the citations are read from change
+async fn watch<Service>(
+    mut args: GetAllArgs,
+    files: Vec<camino::Utf8PathBuf>,
+    dblp: &DblpServerArgs,
+    service: &mut Service,
+    aliases: aliases::Aliases,
+    mut entries: Entries,
+    color: Color,
+) -> Result<()>
+where
+    Service: tower::Service<
+            reqwest::Request,
+            Response = reqwest::Response,
+            Error = Box<dyn std::error::Error + Send + std::marker::Sync + 'static>,
+        > + Clone,
+{
+    let styles = {
+        let mut styles = cli::Styles::default();
+        if color.should_color(&std::io::stderr()) {
+            styles.colorize();
+        }
+        styles
+    };
+    let mut watcher = watch::Watcher::new(files, std::time::Duration::from_millis(args.debounce));
+    loop {
+        eprintln!("{}", "watching for new citations".style(styles.info));
+        watcher.wait().await;
+
+        let cited = match cited_keys(&mut args) {
+            Ok(cited) => cited,
+            Err(err) => {
+                cli::warning!("LaTeX", "failed to read citations: {err}");
+                continue;
+            }
+        };
+        watcher.set_files(cited.files);
+
+        let resolved = aliases.resolve(
+            cited.keys.iter().map(String::as_str),
+            &args.common.key_prefix,
+        );
+        let aliases_changed = resolved
+            .aliases
+            .iter()
+            .any(|(key, alias)| entries.aliases.get(key) != Some(alias));
+        entries.aliases.extend(resolved.aliases);
+        let mut keys: Vec<String> = resolved
+            .keys
+            .into_iter()
+            .filter(|key| {
+                entries
+                    .records
+                    .binary_search_by(|rec| rec.key().cmp(key.as_str()))
+                    .is_err()
+                    && entries
+                        .crossref_recs
+                        .binary_search_by(|rec| rec.key().cmp(key.as_str()))
+                        .is_err()
+                    && !entries.unknown.contains(key)
+            })
+            .collect();
+        let invalid_keys = split_invalid(&mut keys);
+        if !invalid_keys.is_empty() {
+            cli::warning!("DBLP", "invalid DBLP keys:{}", key_list(&invalid_keys));
+            entries
+                .unknown
+                .extend(invalid_keys.into_iter().map(|invalid| invalid.0));
+        }
+        if keys.is_empty() && missing_crossrefs(&entries.records, &entries).is_empty() {
+            if aliases_changed {
+                write_output(&args, &entries, color)?;
+            }
+            continue;
+        }
+
+        // failing requests, e.g., while offline, are retried on the next change
+        let results = match fetch_keys(&keys, dblp, service, &args.common, color).await {
+            Ok(results) => results,
+            Err(err) => {
+                cli::warning!("DBLP", "failed to fetch new entries: {err}");
+                continue;
+            }
+        };
+        let mut unknown_keys = vec![];
+        let mut new_records = vec![];
+        for res in results {
+            match res {
+                FetchRes::Rec(rec) => new_records.push(rec),
+                FetchRes::Unknown(key) => unknown_keys.push(key),
+            }
+        }
+
+        // crossrefs that failed to fetch before are retried along with those of the new records
+        let crossref_keys = missing_crossrefs(entries.records.iter().chain(&new_records), &entries);
+        let results = match fetch_keys(&crossref_keys, dblp, service, &args.common, color).await {
+            Ok(results) => results,
+            Err(err) => {
+                cli::warning!(
+                    "DBLP",
+                    "failed to fetch crossref entries, retrying on the next change: {err}"
+                );
+                vec![]
+            }
+        };
+        for res in results {
+            match res {
+                FetchRes::Rec(rec) => entries.crossref_recs.push(rec),
+                FetchRes::Unknown(key) => unknown_keys.push(key),
+            }
+        }
+        entries
+            .crossref_recs
+            .sort_unstable_by(|a, b| a.key().cmp(b.key()));
+
+        if args.common.crossref {
+            for rec in entries.records.iter_mut().chain(&mut new_records) {
+                if let Some(key) = rec.crossref_key()
+                    && crossref_keys
+                        .binary_search_by(|fetched| fetched.as_str().cmp(key))
+                        .is_ok()
+                    && let Ok(idx) = entries
+                        .crossref_recs
+                        .binary_search_by_key(&key, Record::key)
+                {
+                    fixers::expand_booktitle(
+                        rec,
+                        &entries.crossref_recs[idx],
+                        args.common.venue_style,
+                    );
+                }
+            }
+        }
+
+        if !new_records.is_empty() {
+            eprintln!(
+                "{} {}",
+                "adding new entries:".style(styles.info),
+                new_records.len()
+            );
+        }
+        entries.records.extend(new_records);
+        entries
+            .records
+            .sort_unstable_by(|a, b| a.key().cmp(b.key()));
+        write_output(&args, &entries, color)?;
+
+        if !unknown_keys.is_empty() {
+            cli::warning!("DBLP", "unknown DBLP keys:{}", key_list(&unknown_keys));
+            // not fetched again on later changes
+            entries.unknown.extend(unknown_keys);
+        }
+    }
+}
+
+/// The sorted crossref keys of the records that are neither among the crossref records nor known
+/// to be missing from DBLP
+fn missing_crossrefs<'a>(
+    records: impl IntoIterator<Item = &'a Record>,
+    entries: &Entries,
+) -> Vec<String> {
+    let mut keys: Vec<String> = records
+        .into_iter()
+        .filter_map(Record::crossref_key)
+        .filter(|key| {
+            entries
+                .crossref_recs
+                .binary_search_by(|rec| rec.key().cmp(key))
+                .is_err()
+                && !entries.unknown.contains(*key)
+        })
+        .map(String::from)
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    fn get_all_args(args: &[&str]) -> super::GetAllArgs {
+        let args = super::cli::Args::parse_from(args);
+        let super::cli::Commands::GetAll(get_all) = args.command else {
+            panic!("parsed wrong command");
+        };
+        get_all
+    }
+
+    #[test]
+    fn usage() {
+        use crate::dblp::record::{Crossref, Person};
+
+        let paper = |key: &str, crossref: &str| super::Record::Inproceedings {
+            key: String::from(key),
+            author: vec![Person::from("Jabs, Christoph")],
+            title: String::from("Title"),
+            booktitle: String::from("CP 2023"),
+            year: 2023,
+            pages: None,
+            external: vec![],
+            crossref: Crossref::Key(String::from(crossref)),
+            usera: None,
+        };
+        let proceedings = |key: &str| super::Record::Proceedings {
+            key: String::from(key),
+            editor: vec![],
+            title: String::from("Proceedings"),
+            year: 2023,
+            series: None,
+            volume: None,
+            publisher: None,
+            external: vec![],
+            isbn: vec![],
+        };
+        let records = [
+            paper("conf/cp/JabsBIJ23", "conf/cp/2023"),
+            proceedings("conf/cp/2023"),
+            paper("conf/sat/JabsBJ24", "conf/sat/2024"),
+            proceedings("conf/sat/2024"),
+        ];
+        let cited = [String::from("conf/cp/JabsBIJ23")];
+
+        let usage = super::Usage::new(&records, &cited, false);
+        let used: Vec<_> = records.iter().map(|rec| usage.is_used(rec)).collect();
+        // proceedings are kept while a cited paper crossrefs them
+        assert_eq!(used, [true, true, false, false]);
+        assert_eq!(usage.crossref_keys, ["conf/cp/2023"]);
+
+        let usage = super::Usage::new(&records, &[], true);
+        assert!(records.iter().all(|rec| usage.is_used(rec)));
+    }
+
+    #[test]
+    fn read_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = camino::Utf8Path::from_path(dir.path())
+            .unwrap()
+            .join("keys.txt");
+        std::fs::write(
+            &path,
+            "# papers to cite\nconf/cp/JabsBIJ23\n\n  DBLP:journals/jair/JabsBNJ24 \n   \n  # indented comment\n",
+        )
+        .unwrap();
+        assert_eq!(
+            super::read_keys(&path).unwrap(),
+            ["conf/cp/JabsBIJ23", "DBLP:journals/jair/JabsBNJ24"]
+        );
+    }
+
+    #[test]
+    fn split_invalid() {
+        let mut keys = vec![
+            String::from("conf/cp/JabsBIJ23"),
+            String::from("JabsBIJ23"),
+            String::from("journals/jair/JabsBNJ24"),
+        ];
+        let invalid = super::split_invalid(&mut keys);
+        assert_eq!(keys, ["conf/cp/JabsBIJ23", "journals/jair/JabsBNJ24"]);
+        assert_eq!(
+            invalid,
+            [crate::dblp::InvalidKey(String::from("JabsBIJ23"))]
+        );
+    }
+
+    #[test]
+    fn export_never_overwrites_bibtex() {
+        let bib_files = [camino::Utf8PathBuf::from("refs.bib")];
+        let color = super::Color::Never;
+
+        // the BibTeX file referenced by the document is not picked
+        let mut args = get_all_args(&[
+            "dblp",
+            "get-all",
+            "--output-format=csl-json",
+            "main.tex",
+            "--bib-name=refs",
+        ]);
+        super::choose_bibtex_path(&mut args, &bib_files, color).unwrap();
+        assert_eq!(args.bibtex_path, None);
+
+        for path in ["dblp.bib", "refs.bib"] {
+            let mut args =
+                get_all_args(&["dblp", "get-all", "--output-format=ris", "main.tex", path]);
+            assert!(super::choose_bibtex_path(&mut args, &bib_files, color).is_err());
+        }
+
+        let mut args = get_all_args(&[
+            "dblp",
+            "get-all",
+            "--output-format=ris",
+            "main.tex",
+            "refs.ris",
+        ]);
+        super::choose_bibtex_path(&mut args, &bib_files, color).unwrap();
+        assert_eq!(args.bibtex_path.as_deref(), Some("refs.ris".into()));
+
+        let mut args = get_all_args(&["dblp", "get-all", "main.tex", "--bib-name=refs"]);
+        super::choose_bibtex_path(&mut args, &bib_files, color).unwrap();
+        assert_eq!(args.bibtex_path, Some(bib_files[0].clone()));
+    }
+}
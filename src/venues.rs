@@ -0,0 +1,231 @@
+//! # Venue Names
+//!
+//! Styles for the names of journals and conferences in `journal` and `booktitle` fields, e.g.,
+//! `Journal of Artificial Intelligence Research`, `J. Artif. Intell. Res.` or `JAIR`. Long names
+//! come from DBLP. Abbreviations and acronyms come from DBLP's stream metadata where it provides
+//! them, and otherwise from a built-in table of venues and ISO 4 word abbreviations.
+
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+
+const BUILTIN: &str = include_str!("venues.toml");
+
+/// Words that are dropped from ISO 4 abbreviations, unless they are the first word
+const DROPPED_WORDS: [&str; 9] = ["a", "an", "and", "for", "in", "of", "on", "the", "&"];
+
+lazy_static! {
+    static ref TABLE: Table = toml::from_str(BUILTIN).expect("built-in venue table must be valid");
+}
+
+#[derive(
+    clap::ValueEnum,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Style {
+    /// Full names, e.g., `Journal of Artificial Intelligence Research`
+    #[default]
+    Long,
+    /// DBLP's short names, e.g., `J. Artif. Intell. Res.` or `Proc. CP 2023`
+    Short,
+    /// ISO 4 abbreviations, e.g., `J. Artif. Intell. Res.`
+    Iso4,
+    /// Acronyms, e.g., `JAIR` or `CP 2023`
+    Acronym,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Table {
+    journals: HashMap<String, Venue>,
+    conferences: HashMap<String, Venue>,
+    words: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Venue {
+    acronym: Option<String>,
+    iso4: Option<String>,
+}
+
+/// Metadata of a journal from its DBLP stream
+#[derive(Clone, Debug, Default)]
+pub struct Journal {
+    pub title: String,
+    pub acronym: Option<String>,
+    pub iso4: Option<String>,
+}
+
+/// Abbreviates a name word by word following ISO 4
+///
+/// Words without a known abbreviation and text in braces are kept.
+pub fn iso4(name: &str) -> String {
+    let mut out = vec![];
+    let mut depth = 0usize;
+    for (idx, word) in name.split_whitespace().enumerate() {
+        let braced = depth > 0 || word.starts_with('{');
+        for ch in word.chars() {
+            match ch {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        if braced {
+            out.push(String::from(word));
+            continue;
+        }
+        if idx > 0 && DROPPED_WORDS.contains(&word.to_lowercase().as_str()) {
+            continue;
+        }
+        // keep punctuation like trailing commas
+        let end = word
+            .find(|ch: char| !ch.is_alphabetic())
+            .unwrap_or(word.len());
+        let (stem, rest) = word.split_at(end);
+        match TABLE.words.get(stem) {
+            Some(abbrev) if !rest.starts_with('.') => out.push(format!("{abbrev}{rest}")),
+            _ => out.push(String::from(word)),
+        }
+    }
+    out.join(" ")
+}
+
+/// The name of a journal in the given style
+///
+/// `key` is the DBLP stream key, e.g., `jair`, and `short` DBLP's short name of the journal.
+/// The stream metadata is only needed for styles other than [`Style::Short`].
+pub fn journal(style: Style, key: &str, short: &str, stream: Option<&Journal>) -> String {
+    let known = TABLE.journals.get(key);
+    let iso4 = || {
+        known
+            .and_then(|venue| venue.iso4.clone())
+            .or_else(|| stream.and_then(|stream| stream.iso4.clone()))
+            .unwrap_or_else(|| String::from(short))
+    };
+    match style {
+        Style::Long => stream.map_or_else(|| String::from(short), |stream| stream.title.clone()),
+        Style::Short => String::from(short),
+        Style::Iso4 => iso4(),
+        Style::Acronym => known
+            .and_then(|venue| venue.acronym.clone())
+            .or_else(|| stream.and_then(|stream| stream.acronym.clone()))
+            .unwrap_or_else(iso4),
+    }
+}
+
+/// The acronym of a conference
+///
+/// Taken from the built-in table, otherwise DBLP's booktitle if it is a single word, e.g., `CP` or
+/// `CPAIOR (2)`, otherwise the upper-cased stream key.
+fn acronym(key: &str, booktitle: &str) -> String {
+    if let Some(acronym) = TABLE
+        .conferences
+        .get(key)
+        .and_then(|venue| venue.acronym.clone())
+    {
+        return acronym;
+    }
+    // strip the volume, e.g., `(2)`
+    let booktitle = booktitle
+        .split_once(" (")
+        .map_or(booktitle, |(name, _)| name)
+        .trim();
+    if !booktitle.is_empty() && !booktitle.contains(char::is_whitespace) {
+        return String::from(booktitle);
+    }
+    key.to_uppercase()
+}
+
+/// The booktitle of a conference paper in the given style
+///
+/// `key` is the DBLP stream key, e.g., `cp`, `booktitle` DBLP's short booktitle and `title` the
+/// title of the proceedings.
+pub fn booktitle(style: Style, key: &str, booktitle: &str, title: &str, year: u32) -> String {
+    match style {
+        Style::Long => String::from(title),
+        Style::Short => format!("Proc. {} {year}", acronym(key, booktitle)),
+        Style::Iso4 => iso4(title),
+        Style::Acronym => format!("{} {year}", acronym(key, booktitle)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Journal, Style};
+
+    #[test]
+    fn journals() {
+        let stream = Journal {
+            title: String::from("Journal of Artificial Intelligence Research"),
+            acronym: None,
+            iso4: None,
+        };
+        let name = |style| super::journal(style, "jair", "J. Artif. Intell. Res.", Some(&stream));
+        assert_eq!(
+            name(Style::Long),
+            "Journal of Artificial Intelligence Research"
+        );
+        assert_eq!(name(Style::Short), "J. Artif. Intell. Res.");
+        assert_eq!(name(Style::Iso4), "J. Artif. Intell. Res.");
+        assert_eq!(name(Style::Acronym), "JAIR");
+
+        // unknown journal, falling back to stream metadata and DBLP's short name
+        let stream = Journal {
+            title: String::from("Some Journal of Logic"),
+            acronym: Some(String::from("SJL")),
+            iso4: None,
+        };
+        let name = |style| super::journal(style, "sjl", "Some J. Log.", Some(&stream));
+        assert_eq!(name(Style::Iso4), "Some J. Log.");
+        assert_eq!(name(Style::Acronym), "SJL");
+    }
+
+    #[test]
+    fn booktitles() {
+        let title = "Principles and Practice of Constraint Programming - 29th International \
+                     Conference, CP 2023, Toronto, Canada, August 27-31, 2023";
+        let name = |style| super::booktitle(style, "cp", "CP", title, 2023);
+        assert_eq!(name(Style::Long), title);
+        assert_eq!(
+            super::iso4("Journal of {Constraint Programming} Research"),
+            "J. {Constraint Programming} Res."
+        );
+        assert_eq!(name(Style::Short), "Proc. CP 2023");
+        assert_eq!(name(Style::Acronym), "CP 2023");
+        assert_eq!(
+            name(Style::Iso4),
+            "Princ. Pract. Constraint Program. - 29th Int. Conf., CP 2023, Toronto, Canada, \
+             August 27-31, 2023"
+        );
+
+        assert_eq!(
+            super::booktitle(Style::Acronym, "cpaior", "CPAIOR (2)", "", 2024),
+            "CPAIOR 2024"
+        );
+        assert_eq!(
+            super::booktitle(Style::Acronym, "nips", "NeurIPS", "", 2023),
+            "NeurIPS 2023"
+        );
+        // booktitle already expanded, e.g., when read from a BibTeX file
+        assert_eq!(
+            super::booktitle(
+                Style::Short,
+                "sat",
+                "Theory and Applications of SAT",
+                "",
+                2024
+            ),
+            "Proc. SAT 2024"
+        );
+    }
+}
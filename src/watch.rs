@@ -0,0 +1,154 @@
+//! # Watch Mode
+//!
+//! Polls files for modifications, used to keep the bibliography in sync while writing
+
+use std::time::{Duration, SystemTime};
+
+/// Interval in which to check the watched files for modifications
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+pub struct Watcher {
+    files: Vec<(camino::Utf8PathBuf, Option<SystemTime>)>,
+    debounce: Duration,
+}
+
+fn modified(path: &camino::Utf8Path) -> Option<SystemTime> {
+    path.metadata().and_then(|meta| meta.modified()).ok()
+}
+
+impl Watcher {
+    pub fn new<I: IntoIterator<Item = camino::Utf8PathBuf>>(files: I, debounce: Duration) -> Self {
+        let mut watcher = Self {
+            files: vec![],
+            debounce,
+        };
+        watcher.set_files(files);
+        watcher
+    }
+
+    /// Updates the set of watched files
+    ///
+    /// Files that were watched before keep their last seen modification time, new files are
+    /// considered unchanged at their current modification time.
+    pub fn set_files<I: IntoIterator<Item = camino::Utf8PathBuf>>(&mut self, files: I) {
+        let mut new_files: Vec<_> = files
+            .into_iter()
+            .map(|path| {
+                let mtime = self
+                    .files
+                    .iter()
+                    .find(|(known, _)| *known == path)
+                    .map_or_else(|| modified(&path), |(_, mtime)| *mtime);
+                (path, mtime)
+            })
+            .collect();
+        new_files.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        new_files.dedup_by(|a, b| a.0 == b.0);
+        self.files = new_files;
+    }
+
+    /// Checks whether any of the files has been modified since the last check
+    pub fn changed(&mut self) -> bool {
+        let mut changed = false;
+        for (path, mtime) in &mut self.files {
+            let current = modified(path);
+            if current != *mtime {
+                *mtime = current;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Waits until any of the files is modified and no further modifications happened for the
+    /// debounce duration
+    pub async fn wait(&mut self) {
+        while !self.changed() {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        loop {
+            tokio::time::sleep(self.debounce).await;
+            if !self.changed() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    fn touch(path: &camino::Utf8Path, content: &str, mtime: SystemTime) {
+        std::fs::write(path, content).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+    }
+
+    #[test]
+    fn detects_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = camino::Utf8Path::from_path(dir.path()).unwrap();
+        let main = dir.join("main.aux");
+        let sub = dir.join("chapter.aux");
+        let start = SystemTime::now() - Duration::from_secs(60);
+        touch(&main, r"\citation{DBLP:conf/cp/JabsBIJ23}", start);
+
+        let mut watcher = super::Watcher::new([main.clone()], Duration::ZERO);
+        assert!(!watcher.changed());
+
+        touch(
+            &main,
+            r"\citation{DBLP:conf/cp/JabsBIJ23}\n\@input{chapter.aux}",
+            start + Duration::from_secs(1),
+        );
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        // newly watched files are not considered changed
+        touch(&sub, r"\citation{knuth1984}", start);
+        watcher.set_files([main.clone(), sub.clone()]);
+        assert!(!watcher.changed());
+
+        // files reached more than once are watched once
+        watcher.set_files([sub.clone(), main.clone(), sub.clone()]);
+        assert_eq!(watcher.files.len(), 2);
+
+        touch(&sub, "", start + Duration::from_secs(2));
+        assert!(watcher.changed());
+
+        // removal is a change
+        std::fs::remove_file(&sub).unwrap();
+        assert!(watcher.changed());
+    }
+
+    #[tokio::test]
+    async fn wait_returns_after_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = camino::Utf8Path::from_path(dir.path()).unwrap();
+        let main = dir.join("main.aux");
+        let start = SystemTime::now() - Duration::from_secs(60);
+        touch(&main, "", start);
+
+        let mut watcher = super::Watcher::new([main.clone()], Duration::from_millis(10));
+        let writer = tokio::spawn({
+            let main = main.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                touch(
+                    &main,
+                    r"\citation{knuth1984}",
+                    start + Duration::from_secs(1),
+                );
+            }
+        });
+        tokio::time::timeout(Duration::from_secs(5), watcher.wait())
+            .await
+            .expect("change should be detected");
+        writer.await.unwrap();
+    }
+}